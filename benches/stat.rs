@@ -0,0 +1,114 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use game_stat::prelude::*;
+use game_stat::StatSheet;
+
+// a mix of kinds added in reverse order, so every recalculation has to sort
+fn filled_stat<const M: usize>() -> (Stat<M>, Vec<StatModifierHandle>) {
+    let mut stat: Stat<M> = Stat::new(10.0);
+    let handles = (0..M)
+        .map(|i| {
+            let modifier = match i % 3 {
+                0 => StatModifier::Flat(i as f32),
+                1 => StatModifier::PercentAdd(0.1),
+                _ => StatModifier::PercentMultiply(1.1),
+            };
+            stat.add_modifier_with_order(modifier, (M - i) as i32)
+        })
+        .collect();
+    (stat, handles)
+}
+
+fn bench_capacity<const M: usize>(c: &mut Criterion) {
+    let mut group = c.benchmark_group("stat");
+
+    group.bench_function(BenchmarkId::new("add_modifier", M), |b| {
+        b.iter(|| {
+            let (stat, handles) = filled_stat::<M>();
+            black_box((stat.value(), handles.len()))
+        })
+    });
+
+    group.bench_function(BenchmarkId::new("value_cached", M), |b| {
+        let (stat, _handles) = filled_stat::<M>();
+        b.iter(|| black_box(stat.value()))
+    });
+
+    group.bench_function(BenchmarkId::new("value_dirty", M), |b| {
+        let (mut stat, _handles) = filled_stat::<M>();
+        b.iter(|| {
+            *stat.base_value_mut() += 1.0;
+            black_box(stat.value())
+        })
+    });
+
+    // dropping a handle makes the next read prune and recalculate
+    group.bench_function(BenchmarkId::new("value_after_drop", M), |b| {
+        let (mut stat, mut handles) = filled_stat::<M>();
+        b.iter(|| {
+            handles.pop();
+            handles.push(stat.add_modifier_with_order(StatModifier::Flat(1.0), -1));
+            black_box(stat.value())
+        })
+    });
+
+    group.bench_function(BenchmarkId::new("value_with_base", M), |b| {
+        let (stat, _handles) = filled_stat::<M>();
+        b.iter(|| black_box(stat.value_with_base(black_box(20.0))))
+    });
+
+    group.finish();
+}
+
+fn sheet(stats: usize) -> StatSheet<usize, 4> {
+    let mut sheet = StatSheet::new();
+    for key in 0..stats {
+        sheet.insert(key, Stat::new(10.0));
+    }
+    sheet
+}
+
+// every stat gets a modifier but only one is read, recompute_dirty pays for all of them
+fn bench_sheet(c: &mut Criterion, stats: usize) {
+    let mut group = c.benchmark_group("sheet");
+
+    group.bench_function(BenchmarkId::new("mutate_all_read_one", stats), |b| {
+        b.iter_batched(
+            || sheet(stats),
+            |mut sheet| {
+                let handles: Vec<StatModifierHandle> = (0..stats)
+                    .map(|key| sheet.add_modifier(&key, StatModifier::Flat(1.0)).unwrap())
+                    .collect();
+                black_box(sheet.get(&0).unwrap().value());
+                (sheet, handles)
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function(BenchmarkId::new("mutate_all_recompute_dirty", stats), |b| {
+        b.iter_batched(
+            || sheet(stats),
+            |mut sheet| {
+                let handles: Vec<StatModifierHandle> = (0..stats)
+                    .map(|key| sheet.add_modifier(&key, StatModifier::Flat(1.0)).unwrap())
+                    .collect();
+                sheet.recompute_dirty();
+                black_box(sheet.get(&0).unwrap().value());
+                (sheet, handles)
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+fn benches(c: &mut Criterion) {
+    bench_capacity::<4>(c);
+    bench_capacity::<8>(c);
+    bench_capacity::<16>(c);
+    bench_sheet(c, 64);
+}
+
+criterion_group!(stat_benches, benches);
+criterion_main!(stat_benches);
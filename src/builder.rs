@@ -0,0 +1,127 @@
+use crate::ref_count::{DefaultStrategy, RefCountStrategy};
+use crate::stat::{Rounding, Stat, TieBreak};
+
+/// Configures everything about a [`Stat`] in one go, created through [`Stat::builder()`].
+/// The capacity is the `M` of the stat: ```Stat::<8>::builder()```
+///
+/// ```
+/// # use game_stat::prelude::*;
+/// # use game_stat::Rounding;
+/// let mut attack = Stat::<4>::builder()
+///     .base(10.0)
+///     .bounds(0.0, 100.0)
+///     .max_multiplier(3.0)
+///     .rounding(Rounding::Round)
+///     .build();
+/// let _handle = attack.add_modifier(StatModifier::PercentMultiply(1.26));
+/// assert_eq!(attack.value(), 13.0);
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct StatBuilder<const M: usize, S: RefCountStrategy = DefaultStrategy> {
+    base_value: f32,
+    min_value: Option<f32>,
+    max_value: Option<f32>,
+    clamp_between_phases: bool,
+    min_multiplier: Option<f32>,
+    max_multiplier: Option<f32>,
+    rounding: Option<Rounding>,
+    step_rounding: Option<Rounding>,
+    tie_break: TieBreak,
+    f64_accumulation: bool,
+    override_value: Option<f32>,
+    strategy: std::marker::PhantomData<S>,
+}
+
+impl<const M: usize, S: RefCountStrategy> StatBuilder<M, S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// defaults to 0
+    pub fn base(mut self, base_value: f32) -> Self {
+        self.base_value = base_value;
+        self
+    }
+
+    /// see [`Stat::set_bounds()`]
+    pub fn bounds(mut self, min: f32, max: f32) -> Self {
+        self.min_value = Some(min);
+        self.max_value = Some(max);
+        self
+    }
+
+    pub fn min(mut self, min: f32) -> Self {
+        self.min_value = Some(min);
+        self
+    }
+
+    pub fn max(mut self, max: f32) -> Self {
+        self.max_value = Some(max);
+        self
+    }
+
+    /// see [`Stat::set_clamp_between_phases()`]
+    pub fn clamp_between_phases(mut self, clamp_between_phases: bool) -> Self {
+        self.clamp_between_phases = clamp_between_phases;
+        self
+    }
+
+    /// see [`Stat::set_min_multiplier()`]
+    pub fn min_multiplier(mut self, min_multiplier: f32) -> Self {
+        self.min_multiplier = Some(min_multiplier);
+        self
+    }
+
+    /// see [`Stat::set_max_multiplier()`]
+    pub fn max_multiplier(mut self, max_multiplier: f32) -> Self {
+        self.max_multiplier = Some(max_multiplier);
+        self
+    }
+
+    /// see [`Stat::set_rounding()`]
+    pub fn rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = Some(rounding);
+        self
+    }
+
+    /// see [`Stat::set_step_rounding()`]
+    pub fn step_rounding(mut self, step_rounding: Rounding) -> Self {
+        self.step_rounding = Some(step_rounding);
+        self
+    }
+
+    /// see [`Stat::set_tie_break()`]
+    pub fn tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// see [`Stat::set_f64_accumulation()`]
+    pub fn f64_accumulation(mut self, f64_accumulation: bool) -> Self {
+        self.f64_accumulation = f64_accumulation;
+        self
+    }
+
+    /// see [`Stat::set_override()`]
+    pub fn override_value(mut self, value: f32) -> Self {
+        self.override_value = Some(value);
+        self
+    }
+
+    pub fn build(self) -> Stat<M, S> {
+        let mut stat = Stat::new(self.base_value);
+        stat.min_value = self.min_value;
+        stat.max_value = self.max_value;
+        stat.clamp_between_phases = self.clamp_between_phases;
+        stat.min_multiplier = self.min_multiplier;
+        stat.max_multiplier = self.max_multiplier;
+        stat.rounding = self.rounding;
+        stat.step_rounding = self.step_rounding;
+        stat.tie_break = self.tie_break;
+        stat.f64_accumulation = self.f64_accumulation;
+        stat.override_value = self.override_value;
+        // the setters would recalculate one by one
+        stat.calculate_internal_value();
+        stat
+    }
+}
@@ -15,14 +15,44 @@
 //! * We add a [`StatModifier`], it is valid as long as the [`StatModifierHandle`] that is returned from [`Stat::add_modifier()`] exists, which is why our value goes back to 10 when it gets dropped from the stack
 //!
 //! # crate features:
-//! **sync**: if Stat is needed in a multithreaded environment, enable this
+//! **sync**: if Stat is needed in a multithreaded environment, enable this. Makes [`ArcStrategy`] the [`DefaultStrategy`],
+//! without it single stats can still opt in through [`SyncStat`] (and [`LocalStat`] the other way around)
+//!
+//! **metrics**: counts how often stats recalculate their value, see [`metrics`]
+//!
+//! **manual-pruning**: modifiers are no longer removed when their handle is dropped, only through ```stat.remove_modifier()```.
+//! Trades the automatic cleanup for full control over when a modifier goes away.
+//! A [`ModifierScheduler`] can't reach the stats, expire its modifiers through ```scheduler.advance_expired()``` and ```stat.remove_modifier()```
+//!
+//! **serde**: serialize and deserialize stats and modifiers, any serde format works (modifier sets authored in RON for example)
+//!
+//! **rand**: helpers for rolling modifiers with random values, like ```StatModifier::roll_flat()``` and ```stat.with_jitter()```
+//!
+//! **history**: stats remember their latest values for graphing, see ```Stat::history()```
+//!
+//! **tracing**: emits trace events when modifiers are added or pruned and when a value is calculated.
+//! Events carry the address of the stat to tell them apart, which changes if the stat is moved
 
+mod builder;
+mod linked;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 mod modifier;
+mod ref_count;
+mod scheduler;
+mod sheet;
 mod stat;
+mod stat_like;
+pub use crate::builder::*;
+pub use crate::linked::*;
 pub use crate::modifier::*;
+pub use crate::ref_count::*;
+pub use crate::scheduler::*;
+pub use crate::sheet::*;
 pub use crate::stat::*;
+pub use crate::stat_like::*;
 
 pub mod prelude {
-    pub use crate::modifier::StatModifier;
+    pub use crate::modifier::{ModifierDef, StatModifier};
     pub use crate::stat::{Stat, StatModifierHandle};
 }
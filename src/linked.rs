@@ -0,0 +1,109 @@
+use crate::modifier::StatModifier;
+use crate::ref_count::{DefaultStrategy, RefCountStrategy};
+use crate::stat::{Stat, StatModifierHandle};
+
+/// A base value shared between [`LinkedStat`]s, cloning it gives another handle to the same value
+#[derive(Clone, Debug, Default)]
+pub struct SharedBase<S: RefCountStrategy = DefaultStrategy>(S::SharedF32);
+
+impl SharedBase {
+    /// A base using the [`DefaultStrategy`], ```SharedBase::<RcStrategy>::with_value()``` picks another one
+    pub fn new(base_value: f32) -> Self {
+        Self::with_value(base_value)
+    }
+}
+
+impl<S: RefCountStrategy> SharedBase<S> {
+    pub fn with_value(base_value: f32) -> Self {
+        Self(S::new_shared_f32(base_value))
+    }
+
+    pub fn get(&self) -> f32 {
+        S::get_shared_f32(&self.0)
+    }
+
+    /// every [`LinkedStat`] using this base picks up the new value the next time it's read
+    pub fn set(&self, base_value: f32) {
+        S::set_shared_f32(&self.0, base_value)
+    }
+}
+
+/// A stat whose base value is shared with other linked stats, while every one of them has its own modifiers.
+/// Think of a shield shown in two places, each with their own buffs on top
+///
+/// ```
+/// # use game_stat::prelude::*;
+/// # use game_stat::{LinkedStat, SharedBase};
+/// let shield = SharedBase::new(10.0);
+/// let mut hud_shield: LinkedStat<2> = LinkedStat::new(&shield);
+/// let mut party_shield: LinkedStat<2> = LinkedStat::new(&shield);
+/// let _handle = party_shield.add_modifier(StatModifier::Flat(5.0));
+///
+/// shield.set(20.0);
+/// assert_eq!(hud_shield.value(), 20.0);
+/// assert_eq!(party_shield.value(), 25.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LinkedStat<const M: usize, S: RefCountStrategy = DefaultStrategy> {
+    base: SharedBase<S>,
+    stat: Stat<M, S>,
+}
+
+impl<const M: usize, S: RefCountStrategy> LinkedStat<M, S> {
+    pub fn new(base: &SharedBase<S>) -> Self {
+        Self {
+            base: base.clone(),
+            stat: Stat::new(base.get()),
+        }
+    }
+
+    /// Returns the base shared with the other linked stats
+    pub fn base(&self) -> &SharedBase<S> {
+        &self.base
+    }
+
+    /// Sets the base value of this stat and every stat linked to it
+    pub fn set_base(&self, base_value: f32) {
+        self.base.set(base_value);
+    }
+
+    /// Returns the stat holding the modifiers, with the shared base value already applied.
+    /// Don't change ```base_value``` on it, the shared base overwrites it
+    /// panics if refcell is borrowed
+    pub fn stat_mut(&mut self) -> &mut Stat<M, S> {
+        self.sync_base();
+        &mut self.stat
+    }
+
+    /// see [`Stat::add_modifier()`]
+    /// panics if refcell is borrowed
+    pub fn add_modifier(&mut self, modifier: StatModifier) -> StatModifierHandle<S> {
+        self.stat_mut().add_modifier(modifier)
+    }
+
+    /// see [`Stat::add_modifier_with_order()`]
+    /// panics if refcell is borrowed
+    pub fn add_modifier_with_order(
+        &mut self,
+        modifier: StatModifier,
+        order: i32,
+    ) -> StatModifierHandle<S> {
+        self.stat_mut().add_modifier_with_order(modifier, order)
+    }
+
+    /// Returns the shared base value with this stat's modifiers applied,
+    /// only recalculates if the shared base or the modifiers changed
+    /// panics if refcell is borrowed
+    pub fn value(&mut self) -> f32 {
+        self.sync_base();
+        self.stat.value()
+    }
+
+    fn sync_base(&mut self) {
+        let base_value = self.base.get();
+        if base_value.to_bits() != self.stat.base_value.to_bits() {
+            self.stat.base_value = base_value;
+            self.stat.mark_dirty();
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! Coarse counters for catching performance regressions, enabled with the **metrics** feature.
+//!
+//! The counters are global and shared by every [`super::Stat`], so you probably want them in an integration test
+//! of their own, where other tests can't run in parallel and bump the count.
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static RECOMPUTE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+#[inline]
+pub(crate) fn record_recompute() {
+    RECOMPUTE_COUNT.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Returns how many times any stat has recalculated its value since start (or the last reset)
+pub fn recompute_count() -> usize {
+    RECOMPUTE_COUNT.load(Ordering::Relaxed)
+}
+
+/// Sets the recompute counter back to 0
+pub fn reset_recompute_count() {
+    RECOMPUTE_COUNT.store(0, Ordering::Relaxed);
+}
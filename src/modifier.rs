@@ -8,6 +8,72 @@ pub enum StatModifier {
     PercentAdd(f32),
     /// Direct multiplication. StatModifier::```PercentMultiply(0.5)``` the value is **halved**
     PercentMultiply(f32),
+    /// Raise the value to a minimum at this point of the order. ```StatModifier::Floor(5.0)``` the value is **at least 5.0**
+    Floor(f32),
+    /// Lower the value to a maximum at this point of the order. ```StatModifier::Ceil(5.0)``` the value is **at most 5.0**
+    Ceil(f32),
+    /// Diminishing returns, every point is worth a bit less than the previous one: ```value / (1.0 + rate * |value|)```.
+    /// ```StatModifier::Diminishing(0.01)``` turns 100 into **50**, and 300 into **75**. Applied last by default
+    Diminishing(f32),
+    /// "increased" like in Path of Exile, adds up with the other increased modifiers of the same order before multiplying once.
+    /// ```StatModifier::Increased(0.5)``` and ```StatModifier::Increased(0.3)``` together multiply by **1.8**
+    Increased(f32),
+    /// "more" like in Path of Exile, every one multiplies on its own. ```StatModifier::More(0.2)``` the value is **1.2 times larger**.
+    /// Applied after increased modifiers by default
+    More(f32),
+}
+
+/// The variant of a [`StatModifier`] without its value.
+/// Casting to `u8` (```ModifierKind::Flat as u8```) gives a stable id, for FFI or compact formats
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum ModifierKind {
+    Flat = 0,
+    PercentAdd = 1,
+    PercentMultiply = 2,
+    Floor = 3,
+    Ceil = 4,
+    Diminishing = 5,
+    Increased = 6,
+    More = 7,
+}
+
+/// returns the id back if it doesn't belong to a kind
+impl TryFrom<u8> for ModifierKind {
+    type Error = u8;
+
+    fn try_from(id: u8) -> Result<Self, Self::Error> {
+        Ok(match id {
+            0 => ModifierKind::Flat,
+            1 => ModifierKind::PercentAdd,
+            2 => ModifierKind::PercentMultiply,
+            3 => ModifierKind::Floor,
+            4 => ModifierKind::Ceil,
+            5 => ModifierKind::Diminishing,
+            6 => ModifierKind::Increased,
+            7 => ModifierKind::More,
+            _ => return Err(id),
+        })
+    }
+}
+
+/// Compares the kind and the bit pattern of the value, so modifiers can be deduplicated or used as map keys.
+/// Unlike comparing floats with `==`, ```Flat(f32::NAN)``` equals itself and ```Flat(0.0)``` doesn't equal ```Flat(-0.0)```
+impl PartialEq for StatModifier {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind() == other.kind() && self.value().to_bits() == other.value().to_bits()
+    }
+}
+
+impl Eq for StatModifier {}
+
+/// Hashes the kind and the bit pattern of the value, matching [`PartialEq`]
+impl std::hash::Hash for StatModifier {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.kind().hash(state);
+        self.value().to_bits().hash(state);
+    }
 }
 
 impl Default for StatModifier {
@@ -23,6 +89,141 @@ impl StatModifier {
             StatModifier::Flat(v) => *value += v,
             StatModifier::PercentAdd(v) => *value *= 1.0f32 + v,
             StatModifier::PercentMultiply(v) => *value *= v,
+            StatModifier::Floor(v) => *value = value.max(*v),
+            StatModifier::Ceil(v) => *value = value.min(*v),
+            StatModifier::Diminishing(rate) => *value /= 1.0 + rate * value.abs(),
+            StatModifier::Increased(v) | StatModifier::More(v) => *value *= 1.0f32 + v,
+        }
+    }
+
+    /// Same as [`StatModifier::apply()`] for an `f64`, the value of the modifier is widened first
+    pub fn apply_f64(&self, value: &mut f64) {
+        match *self {
+            StatModifier::Flat(v) => *value += v as f64,
+            StatModifier::PercentAdd(v) => *value *= 1.0 + v as f64,
+            StatModifier::PercentMultiply(v) => *value *= v as f64,
+            StatModifier::Floor(v) => *value = value.max(v as f64),
+            StatModifier::Ceil(v) => *value = value.min(v as f64),
+            StatModifier::Diminishing(rate) => *value /= 1.0 + rate as f64 * value.abs(),
+            StatModifier::Increased(v) | StatModifier::More(v) => *value *= 1.0 + v as f64,
+        }
+    }
+
+    /// Creates a [`StatModifier::Increased`], ```StatModifier::increased(0.5)``` is 50% increased
+    pub fn increased(value: f32) -> Self {
+        StatModifier::Increased(value)
+    }
+
+    /// Creates a [`StatModifier::More`], ```StatModifier::more(0.2)``` is 20% more
+    pub fn more(value: f32) -> Self {
+        StatModifier::More(value)
+    }
+
+    /// Creates a flat modifier with a value picked from `range`, like a random affix on loot
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let modifier = StatModifier::roll_flat(5.0..10.0, &mut rand::thread_rng());
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn roll_flat<R: rand::Rng + ?Sized>(
+        range: impl rand::distributions::uniform::SampleRange<f32>,
+        rng: &mut R,
+    ) -> Self {
+        StatModifier::Flat(rng.gen_range(range))
+    }
+
+    /// Creates a [`StatModifier::PercentAdd`] with a value picked from `range`
+    #[cfg(feature = "rand")]
+    pub fn roll_percent<R: rand::Rng + ?Sized>(
+        range: impl rand::distributions::uniform::SampleRange<f32>,
+        rng: &mut R,
+    ) -> Self {
+        StatModifier::PercentAdd(rng.gen_range(range))
+    }
+
+    /// Creates the modifier of the given kind holding `value`
+    pub fn from_kind(kind: ModifierKind, value: f32) -> Self {
+        match kind {
+            ModifierKind::Flat => StatModifier::Flat(value),
+            ModifierKind::PercentAdd => StatModifier::PercentAdd(value),
+            ModifierKind::PercentMultiply => StatModifier::PercentMultiply(value),
+            ModifierKind::Floor => StatModifier::Floor(value),
+            ModifierKind::Ceil => StatModifier::Ceil(value),
+            ModifierKind::Diminishing => StatModifier::Diminishing(value),
+            ModifierKind::Increased => StatModifier::Increased(value),
+            ModifierKind::More => StatModifier::More(value),
+        }
+    }
+
+    /// Returns the variant, without the value
+    pub fn kind(&self) -> ModifierKind {
+        match self {
+            StatModifier::Flat(_) => ModifierKind::Flat,
+            StatModifier::PercentAdd(_) => ModifierKind::PercentAdd,
+            StatModifier::PercentMultiply(_) => ModifierKind::PercentMultiply,
+            StatModifier::Floor(_) => ModifierKind::Floor,
+            StatModifier::Ceil(_) => ModifierKind::Ceil,
+            StatModifier::Diminishing(_) => ModifierKind::Diminishing,
+            StatModifier::Increased(_) => ModifierKind::Increased,
+            StatModifier::More(_) => ModifierKind::More,
+        }
+    }
+
+    /// Returns the number the variant holds
+    pub fn value(&self) -> f32 {
+        match self {
+            StatModifier::Flat(v)
+            | StatModifier::PercentAdd(v)
+            | StatModifier::PercentMultiply(v)
+            | StatModifier::Floor(v)
+            | StatModifier::Ceil(v)
+            | StatModifier::Diminishing(v)
+            | StatModifier::Increased(v)
+            | StatModifier::More(v) => *v,
+        }
+    }
+
+    /// Returns true if the modifier never changes the value, like ```StatModifier::Flat(0.0)``` or ```StatModifier::PercentMultiply(1.0)```.
+    /// Handy for hiding inert modifiers in a tooltip
+    pub fn is_no_op(&self) -> bool {
+        match self {
+            StatModifier::Flat(v)
+            | StatModifier::PercentAdd(v)
+            | StatModifier::Diminishing(v)
+            | StatModifier::Increased(v)
+            | StatModifier::More(v) => *v == 0.0,
+            StatModifier::PercentMultiply(v) => *v == 1.0,
+            StatModifier::Floor(v) => *v == f32::NEG_INFINITY,
+            StatModifier::Ceil(v) => *v == f32::INFINITY,
+        }
+    }
+
+    /// Returns how far the modifier moves the value, no matter the direction: ```Flat(-5.0)``` is stronger than ```Flat(2.0)```,
+    /// ```PercentMultiply(0.5)``` is as strong as ```PercentMultiply(1.5)```
+    pub(crate) fn strength(&self) -> f32 {
+        match self {
+            StatModifier::PercentMultiply(v) => (v - 1.0).abs(),
+            StatModifier::Flat(v)
+            | StatModifier::PercentAdd(v)
+            | StatModifier::Floor(v)
+            | StatModifier::Ceil(v)
+            | StatModifier::Diminishing(v)
+            | StatModifier::Increased(v)
+            | StatModifier::More(v) => v.abs(),
+        }
+    }
+
+    /// Returns how much the modifier multiplies its input by, None if the modifier isn't a plain scale + offset
+    pub(crate) fn scale(&self) -> Option<f32> {
+        match self {
+            StatModifier::Flat(_) => Some(1.0),
+            StatModifier::PercentAdd(v) | StatModifier::More(v) => Some(1.0 + v),
+            StatModifier::PercentMultiply(v) => Some(*v),
+            // increased modifiers are added up first, the scale depends on the others
+            StatModifier::Floor(_)
+            | StatModifier::Ceil(_)
+            | StatModifier::Diminishing(_)
+            | StatModifier::Increased(_) => None,
         }
     }
 
@@ -30,8 +231,178 @@ impl StatModifier {
     pub fn default_order(&self) -> i32 {
         match self {
             StatModifier::Flat(_) => 0,
-            StatModifier::PercentAdd(_) => 1,
-            StatModifier::PercentMultiply(_) => 2,
+            StatModifier::PercentAdd(_) | StatModifier::Increased(_) => 1,
+            StatModifier::PercentMultiply(_) | StatModifier::More(_) => 2,
+            StatModifier::Floor(_) | StatModifier::Ceil(_) => 0,
+            StatModifier::Diminishing(_) => 3,
+        }
+    }
+}
+
+/// A [`StatModifier`] packed into 8 bytes for network replication, see ```stat.pack_modifiers()```.
+/// The kind is a [`ModifierKind`] id, the order is not part of it
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[repr(C)]
+pub struct PackedModifier {
+    pub kind: u8,
+    _pad: [u8; 3],
+    pub value: f32,
+}
+
+impl PackedModifier {
+    /// Returns the modifier as little endian bytes: the kind, 3 bytes of padding and the value
+    pub fn to_bytes(&self) -> [u8; 8] {
+        let mut bytes = [0; 8];
+        bytes[0] = self.kind;
+        bytes[4..].copy_from_slice(&self.value.to_le_bytes());
+        bytes
+    }
+
+    /// Reads a modifier written by [`PackedModifier::to_bytes()`], the padding is ignored
+    pub fn from_bytes(bytes: [u8; 8]) -> Self {
+        Self {
+            kind: bytes[0],
+            _pad: [0; 3],
+            value: f32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
         }
     }
 }
+
+impl From<StatModifier> for PackedModifier {
+    fn from(modifier: StatModifier) -> Self {
+        Self {
+            kind: modifier.kind() as u8,
+            _pad: [0; 3],
+            value: modifier.value(),
+        }
+    }
+}
+
+/// returns the kind id back if it doesn't belong to a kind
+impl TryFrom<PackedModifier> for StatModifier {
+    type Error = u8;
+
+    fn try_from(packed: PackedModifier) -> Result<Self, Self::Error> {
+        let kind = ModifierKind::try_from(packed.kind)?;
+        Ok(StatModifier::from_kind(kind, packed.value))
+    }
+}
+
+/// Distance between two phases returned from [`spaced_order()`], leaving room to insert orders in between
+pub const ORDER_SPACING: i32 = 1000;
+
+/// Returns the order of a phase spread out by [`ORDER_SPACING`]. Using these instead of 0, 1, 2...
+/// makes it possible to always slot a modifier between two phases with [`order_between()`]
+pub const fn spaced_order(phase: i32) -> i32 {
+    phase * ORDER_SPACING
+}
+
+/// Returns an order halfway between `a` and `b`, None if there is no whole number strictly between them
+/// ```
+/// # use game_stat::{order_between, spaced_order};
+/// assert_eq!(order_between(spaced_order(1), spaced_order(2)), Some(1500));
+/// assert_eq!(order_between(1, 2), None);
+/// ```
+pub fn order_between(a: i32, b: i32) -> Option<i32> {
+    let (low, high) = if a <= b { (a, b) } else { (b, a) };
+    // widened so the distance between i32::MIN and i32::MAX doesn't overflow
+    let between = ((low as i64 + high as i64).div_euclid(2)) as i32;
+    (between > low && between < high).then_some(between)
+}
+
+/// Where a modifier comes from, so every modifier of a category can be removed at once
+/// with ```stat.remove_by_source()``` (like all consumable effects on death)
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum ModifierSource {
+    /// modifiers added without a source
+    #[default]
+    Unspecified,
+    Equipment,
+    Skill,
+    Consumable,
+    Environment,
+}
+
+/// When a conditional modifier applies, checked against the condition value of a [`super::Stat`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Condition {
+    /// applies while the condition value is below the threshold. ```Condition::Below(0.5)``` for "below 50% health"
+    Below(f32),
+    /// applies while the condition value is at or above the threshold
+    AtLeast(f32),
+}
+
+impl Condition {
+    pub fn is_met(&self, condition_value: f32) -> bool {
+        match self {
+            Condition::Below(threshold) => condition_value < *threshold,
+            Condition::AtLeast(threshold) => condition_value >= *threshold,
+        }
+    }
+}
+
+/// An owned description of a modifier, the order it is applied at and what it's labeled with.
+///
+/// Unlike a modifier living inside a [`super::Stat`], a definition has no handle attached,
+/// so it can be stored, copied around and re-added to another stat later.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModifierDef {
+    pub modifier: StatModifier,
+    pub order: i32,
+    /// see ```stat.add_modifier_with_tag()```
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub tag: Option<u64>,
+    /// see ```stat.add_modifier_with_source()```
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "is_unspecified")
+    )]
+    pub source: ModifierSource,
+    /// see ```stat.add_modifier_with_name()```. Not serialized, a borrowed name can't be deserialized into `'static`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub name: Option<&'static str>,
+}
+
+impl ModifierDef {
+    pub fn new(modifier: StatModifier, order: i32) -> Self {
+        Self {
+            modifier,
+            order,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_tag(mut self, tag: u64) -> Self {
+        self.tag = Some(tag);
+        self
+    }
+
+    pub fn with_source(mut self, source: ModifierSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    pub fn with_name(mut self, name: &'static str) -> Self {
+        self.name = Some(name);
+        self
+    }
+}
+
+#[cfg(feature = "serde")]
+fn is_unspecified(source: &ModifierSource) -> bool {
+    *source == ModifierSource::Unspecified
+}
+
+/// creates a definition using the default order. [`StatModifier::default_order()`]
+impl From<StatModifier> for ModifierDef {
+    fn from(modifier: StatModifier) -> Self {
+        Self::new(modifier, modifier.default_order())
+    }
+}
@@ -0,0 +1,190 @@
+use crate::stat::StatModifierHandleTag;
+use std::fmt::Debug;
+use std::ops::DerefMut;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Decides how a [`super::Stat`] shares its modifiers with their handles, picked per stat through its second
+/// generic parameter. [`RcStrategy`] is the cheaper single-threaded one, [`ArcStrategy`] lets stats and handles
+/// move between threads. Both can be used in the same program, see [`super::LocalStat`] and [`super::SyncStat`]
+pub trait RefCountStrategy: sealed::Sealed + Copy + Default + Debug + PartialEq + 'static {
+    #[doc(hidden)]
+    type Handle: Clone + Debug;
+    #[doc(hidden)]
+    type WeakHandle: Clone + Debug + Default;
+    #[doc(hidden)]
+    type SharedF32: Clone + Debug + Default;
+    #[doc(hidden)]
+    type Cell<T: Debug + Default>: Debug + Default;
+    #[doc(hidden)]
+    type CellGuard<'a, T: Debug + 'a>: DerefMut<Target = T>;
+
+    #[doc(hidden)]
+    fn new_handle() -> Self::Handle;
+    #[doc(hidden)]
+    fn downgrade(handle: &Self::Handle) -> Self::WeakHandle;
+    #[doc(hidden)]
+    fn handle_ptr(handle: &Self::Handle) -> *const StatModifierHandleTag;
+    #[doc(hidden)]
+    fn weak_ptr(weak: &Self::WeakHandle) -> *const StatModifierHandleTag;
+    // 0 once every handle is dropped
+    #[doc(hidden)]
+    fn strong_count(weak: &Self::WeakHandle) -> usize;
+
+    #[doc(hidden)]
+    fn new_cell<T: Debug + Default>(value: T) -> Self::Cell<T>;
+    /// panics if refcell is borrowed
+    #[doc(hidden)]
+    fn borrow_cell<T: Debug + Default>(cell: &Self::Cell<T>) -> Self::CellGuard<'_, T>;
+    // a copy that isn't shared with the original
+    #[doc(hidden)]
+    fn clone_cell<T: Debug + Default + Clone>(cell: &Self::Cell<T>) -> Self::Cell<T> {
+        Self::new_cell(Self::borrow_cell(cell).clone())
+    }
+
+    #[doc(hidden)]
+    fn new_shared_f32(value: f32) -> Self::SharedF32;
+    #[doc(hidden)]
+    fn get_shared_f32(shared: &Self::SharedF32) -> f32;
+    #[doc(hidden)]
+    fn set_shared_f32(shared: &Self::SharedF32, value: f32);
+}
+
+/// Single-threaded [`RefCountStrategy`], handles are an ```Rc``` and the stat uses ```RefCell```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::TypePath))]
+pub struct RcStrategy;
+
+/// Thread-safe [`RefCountStrategy`], handles are an ```Arc``` and the stat uses ```Mutex```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "reflect", derive(bevy_reflect::TypePath))]
+pub struct ArcStrategy;
+
+/// The strategy of a [`super::Stat`] that doesn't name one, [`ArcStrategy`] with the **sync** feature, [`RcStrategy`] otherwise
+#[cfg(not(feature = "sync"))]
+pub type DefaultStrategy = RcStrategy;
+/// The strategy of a [`super::Stat`] that doesn't name one, [`ArcStrategy`] with the **sync** feature, [`RcStrategy`] otherwise
+#[cfg(feature = "sync")]
+pub type DefaultStrategy = ArcStrategy;
+
+impl sealed::Sealed for RcStrategy {}
+
+impl RefCountStrategy for RcStrategy {
+    type Handle = std::rc::Rc<StatModifierHandleTag>;
+    type WeakHandle = std::rc::Weak<StatModifierHandleTag>;
+    type SharedF32 = std::rc::Rc<std::cell::Cell<f32>>;
+    type Cell<T: Debug + Default> = std::cell::RefCell<T>;
+    type CellGuard<'a, T: Debug + 'a> = std::cell::RefMut<'a, T>;
+
+    #[inline]
+    fn new_handle() -> Self::Handle {
+        std::rc::Rc::new(StatModifierHandleTag)
+    }
+
+    #[inline]
+    fn downgrade(handle: &Self::Handle) -> Self::WeakHandle {
+        std::rc::Rc::downgrade(handle)
+    }
+
+    #[inline]
+    fn handle_ptr(handle: &Self::Handle) -> *const StatModifierHandleTag {
+        std::rc::Rc::as_ptr(handle)
+    }
+
+    #[inline]
+    fn weak_ptr(weak: &Self::WeakHandle) -> *const StatModifierHandleTag {
+        weak.as_ptr()
+    }
+
+    #[inline]
+    fn strong_count(weak: &Self::WeakHandle) -> usize {
+        weak.strong_count()
+    }
+
+    #[inline]
+    fn new_cell<T: Debug + Default>(value: T) -> Self::Cell<T> {
+        std::cell::RefCell::new(value)
+    }
+
+    #[inline]
+    fn borrow_cell<T: Debug + Default>(cell: &Self::Cell<T>) -> Self::CellGuard<'_, T> {
+        cell.borrow_mut()
+    }
+
+    #[inline]
+    fn new_shared_f32(value: f32) -> Self::SharedF32 {
+        std::rc::Rc::new(std::cell::Cell::new(value))
+    }
+
+    #[inline]
+    fn get_shared_f32(shared: &Self::SharedF32) -> f32 {
+        shared.get()
+    }
+
+    #[inline]
+    fn set_shared_f32(shared: &Self::SharedF32, value: f32) {
+        shared.set(value)
+    }
+}
+
+impl sealed::Sealed for ArcStrategy {}
+
+impl RefCountStrategy for ArcStrategy {
+    type Handle = std::sync::Arc<StatModifierHandleTag>;
+    type WeakHandle = std::sync::Weak<StatModifierHandleTag>;
+    type SharedF32 = std::sync::Arc<std::sync::atomic::AtomicU32>;
+    type Cell<T: Debug + Default> = std::sync::Arc<std::sync::Mutex<T>>;
+    type CellGuard<'a, T: Debug + 'a> = std::sync::MutexGuard<'a, T>;
+
+    #[inline]
+    fn new_handle() -> Self::Handle {
+        std::sync::Arc::new(StatModifierHandleTag)
+    }
+
+    #[inline]
+    fn downgrade(handle: &Self::Handle) -> Self::WeakHandle {
+        std::sync::Arc::downgrade(handle)
+    }
+
+    #[inline]
+    fn handle_ptr(handle: &Self::Handle) -> *const StatModifierHandleTag {
+        std::sync::Arc::as_ptr(handle)
+    }
+
+    #[inline]
+    fn weak_ptr(weak: &Self::WeakHandle) -> *const StatModifierHandleTag {
+        weak.as_ptr()
+    }
+
+    #[inline]
+    fn strong_count(weak: &Self::WeakHandle) -> usize {
+        weak.strong_count()
+    }
+
+    #[inline]
+    fn new_cell<T: Debug + Default>(value: T) -> Self::Cell<T> {
+        std::sync::Arc::new(std::sync::Mutex::new(value))
+    }
+
+    #[inline]
+    fn borrow_cell<T: Debug + Default>(cell: &Self::Cell<T>) -> Self::CellGuard<'_, T> {
+        cell.lock().unwrap()
+    }
+
+    #[inline]
+    fn new_shared_f32(value: f32) -> Self::SharedF32 {
+        std::sync::Arc::new(std::sync::atomic::AtomicU32::new(value.to_bits()))
+    }
+
+    #[inline]
+    fn get_shared_f32(shared: &Self::SharedF32) -> f32 {
+        f32::from_bits(shared.load(std::sync::atomic::Ordering::Relaxed))
+    }
+
+    #[inline]
+    fn set_shared_f32(shared: &Self::SharedF32, value: f32) {
+        shared.store(value.to_bits(), std::sync::atomic::Ordering::Relaxed)
+    }
+}
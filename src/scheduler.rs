@@ -0,0 +1,133 @@
+use crate::ref_count::{DefaultStrategy, RefCountStrategy};
+use crate::stat::StatModifierHandle;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+/// Expires modifiers across many stats from a single ```scheduler.advance()```, instead of ticking every stat.
+/// The scheduler takes ownership of the handles and drops them once their time is up,
+/// which removes the modifiers just like dropping the handle yourself would.
+///
+/// With **manual-pruning** dropping a handle doesn't remove its modifier, so ```scheduler.advance()``` only frees the handles.
+/// Use ```scheduler.advance_expired()``` instead and hand every expired handle to ```stat.remove_modifier()```
+///
+/// ```
+/// # #[cfg(not(feature = "manual-pruning"))]
+/// # {
+/// # use game_stat::prelude::*;
+/// # use game_stat::ModifierScheduler;
+/// let mut scheduler = ModifierScheduler::new();
+/// let mut attack: Stat<2> = Stat::new(10.0);
+/// let mut defense: Stat<2> = Stat::new(10.0);
+/// scheduler.schedule(attack.add_modifier(StatModifier::Flat(5.0)), 1.0);
+/// scheduler.schedule(defense.add_modifier(StatModifier::Flat(5.0)), 2.0);
+/// assert_eq!(scheduler.advance(1.5), 1);
+/// assert_eq!(attack.value(), 10.0);
+/// assert_eq!(defense.value(), 15.0);
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct ModifierScheduler<S: RefCountStrategy = DefaultStrategy> {
+    // f64 so short durations stay accurate after hours of play
+    now: f64,
+    // tie breaker, entries that expire at the same time are dropped in the order they were scheduled
+    next_sequence: u64,
+    entries: BinaryHeap<Reverse<ScheduledHandle<S>>>,
+}
+
+#[derive(Debug)]
+struct ScheduledHandle<S: RefCountStrategy> {
+    expires_at: f64,
+    sequence: u64,
+    handle: StatModifierHandle<S>,
+}
+
+impl<S: RefCountStrategy> PartialEq for ScheduledHandle<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S: RefCountStrategy> Eq for ScheduledHandle<S> {}
+
+impl<S: RefCountStrategy> PartialOrd for ScheduledHandle<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: RefCountStrategy> Ord for ScheduledHandle<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.expires_at
+            .total_cmp(&other.expires_at)
+            .then(self.sequence.cmp(&other.sequence))
+    }
+}
+
+impl ModifierScheduler {
+    /// A scheduler for handles of the [`DefaultStrategy`], ```ModifierScheduler::<RcStrategy>::default()``` picks another one
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S: RefCountStrategy> ModifierScheduler<S> {
+    /// Keeps the modifier of `handle` alive for `duration` more time, counted from now
+    pub fn schedule(&mut self, handle: StatModifierHandle<S>, duration: f32) {
+        self.entries.push(Reverse(ScheduledHandle {
+            expires_at: self.now + duration as f64,
+            sequence: self.next_sequence,
+            handle,
+        }));
+        self.next_sequence += 1;
+    }
+
+    /// Advances time, dropping the handles that expired in the order they expire. Returns how many expired
+    pub fn advance(&mut self, delta_time: f32) -> usize {
+        self.advance_expired(delta_time).len()
+    }
+
+    /// Advances time like ```scheduler.advance()```, but hands back the expired handles in the order they expire
+    /// instead of dropping them. Needed with **manual-pruning**, where the modifiers have to be removed explicitly
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// # use game_stat::ModifierScheduler;
+    /// let mut scheduler = ModifierScheduler::new();
+    /// let mut attack: Stat<2> = Stat::new(10.0);
+    /// scheduler.schedule(attack.add_modifier(StatModifier::Flat(5.0)), 1.0);
+    /// for handle in scheduler.advance_expired(1.0) {
+    ///     # #[cfg(feature = "manual-pruning")]
+    ///     attack.remove_modifier(&handle);
+    /// }
+    /// assert_eq!(attack.value(), 10.0);
+    /// ```
+    pub fn advance_expired(&mut self, delta_time: f32) -> Vec<StatModifierHandle<S>> {
+        self.now += delta_time as f64;
+        let mut expired = Vec::new();
+        while let Some(Reverse(next)) = self.entries.peek() {
+            if next.expires_at > self.now {
+                break;
+            }
+            let Some(Reverse(scheduled)) = self.entries.pop() else {
+                break;
+            };
+            expired.push(scheduled.handle);
+        }
+        expired
+    }
+
+    /// Returns how much time is left until the next handle expires, None if nothing is scheduled
+    pub fn time_until_next(&self) -> Option<f32> {
+        self.entries
+            .peek()
+            .map(|Reverse(next)| (next.expires_at - self.now).max(0.0) as f32)
+    }
+
+    /// Returns how many handles are waiting to expire
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -0,0 +1,203 @@
+use crate::modifier::StatModifier;
+use crate::ref_count::{DefaultStrategy, RefCountStrategy};
+use crate::stat::{Stat, StatModifierHandle};
+use std::borrow::Borrow;
+use std::collections::{hash_map, HashMap};
+use std::hash::Hash;
+
+/// Returned when a [`StatSheet`] can't add a modifier
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StatSheetError {
+    /// There is no stat with the given key in the sheet
+    UnknownStat,
+    /// Adding the modifier would exceed the modifier budget of the sheet, see [`StatSheet::with_modifier_budget()`]
+    ModifierBudgetExceeded,
+}
+
+impl std::fmt::Display for StatSheetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatSheetError::UnknownStat => write!(f, "the sheet has no stat with that key"),
+            StatSheetError::ModifierBudgetExceeded => {
+                write!(f, "the sheet has reached its modifier budget")
+            }
+        }
+    }
+}
+
+impl std::error::Error for StatSheetError {}
+
+/// A collection of stats belonging to the same character, looked up by key.
+///
+/// Modifiers added through the sheet don't recalculate the stat right away, only once the stat is read
+/// (or through [`StatSheet::recompute_dirty()`]), so stats nobody looks at in a frame cost nothing
+///
+/// ```
+/// # use game_stat::prelude::*;
+/// # use game_stat::StatSheet;
+/// let mut sheet: StatSheet<&str, 2> = StatSheet::new();
+/// sheet.insert("armor", Stat::new(10.0));
+/// let _handle = sheet.add_modifier("armor", StatModifier::Flat(5.0)).unwrap();
+/// assert_eq!(sheet.get("armor").unwrap().value(), 15.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StatSheet<K, const M: usize, S: RefCountStrategy = DefaultStrategy> {
+    stats: HashMap<K, Stat<M, S>>,
+    // max amount of valid modifiers across all stats
+    modifier_budget: Option<usize>,
+}
+
+impl<K, const M: usize, S: RefCountStrategy> Default for StatSheet<K, M, S> {
+    fn default() -> Self {
+        Self {
+            stats: HashMap::new(),
+            modifier_budget: None,
+        }
+    }
+}
+
+impl<K: Eq + Hash, const M: usize, S: RefCountStrategy> StatSheet<K, M, S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a sheet that holds at most `budget` valid modifiers in total, no matter how many each stat can hold.
+    /// The budget is checked when adding through the sheet, like [`StatSheet::add_modifier()`]
+    pub fn with_modifier_budget(budget: usize) -> Self {
+        Self {
+            stats: HashMap::new(),
+            modifier_budget: Some(budget),
+        }
+    }
+
+    /// Inserts a stat, returning the stat that was previously stored with that key
+    pub fn insert(&mut self, key: K, stat: Stat<M, S>) -> Option<Stat<M, S>> {
+        self.stats.insert(key, stat)
+    }
+
+    /// Gets the stat with the given key for in-place creation, like ```HashMap::entry()```
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// # use game_stat::StatSheet;
+    /// let mut sheet: StatSheet<&str, 2> = StatSheet::new();
+    /// let _handle = sheet.entry("armor").or_insert(10.0).add_modifier(StatModifier::Flat(5.0));
+    /// assert_eq!(sheet.get("armor").unwrap().value(), 15.0);
+    /// ```
+    pub fn entry(&mut self, key: K) -> StatSheetEntry<'_, K, M, S> {
+        StatSheetEntry(self.stats.entry(key))
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<Stat<M, S>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.stats.remove(key)
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&Stat<M, S>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.stats.get(key)
+    }
+
+    /// modifiers added directly to the returned stat are not checked against the modifier budget
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut Stat<M, S>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.stats.get_mut(key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &Stat<M, S>)> {
+        self.stats.iter()
+    }
+
+    /// Returns the amount of stats in the sheet
+    pub fn len(&self) -> usize {
+        self.stats.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stats.is_empty()
+    }
+
+    /// Returns the amount of valid modifiers across all stats
+    /// panics if refcell is borrowed
+    pub fn modifier_count(&self) -> usize {
+        self.stats.values().map(|stat| stat.len()).sum()
+    }
+
+    /// Add a modifier to the stat using the default order. [`super::StatModifier::default_order()`]
+    /// panics if refcell is borrowed
+    pub fn add_modifier<Q>(
+        &mut self,
+        key: &Q,
+        modifier: StatModifier,
+    ) -> Result<StatModifierHandle<S>, StatSheetError>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.add_modifier_with_order(key, modifier, modifier.default_order())
+    }
+
+    /// panics if refcell is borrowed
+    pub fn add_modifier_with_order<Q>(
+        &mut self,
+        key: &Q,
+        modifier: StatModifier,
+        order: i32,
+    ) -> Result<StatModifierHandle<S>, StatSheetError>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        // an unknown stat is reported before the budget, whatever the modifier count
+        if !self.stats.contains_key(key) {
+            return Err(StatSheetError::UnknownStat);
+        }
+        if let Some(budget) = self.modifier_budget {
+            if self.modifier_count() >= budget {
+                return Err(StatSheetError::ModifierBudgetExceeded);
+            }
+        }
+        let stat = self.stats.get_mut(key).ok_or(StatSheetError::UnknownStat)?;
+        Ok(stat.add_modifier_with_order_lazy(modifier, order))
+    }
+
+    /// Recalculates every stat that changed since it was last read.
+    /// Not needed for correct values, reading a stat recalculates it, but lets you pay the cost at a moment of your choosing
+    /// panics if refcell is borrowed
+    pub fn recompute_dirty(&self) {
+        for stat in self.stats.values() {
+            stat.value();
+        }
+    }
+}
+
+/// A stat in a [`StatSheet`] that might not exist yet, returned from [`StatSheet::entry()`].
+/// Modifiers added through the returned stat are not checked against the modifier budget
+pub struct StatSheetEntry<'a, K, const M: usize, S: RefCountStrategy = DefaultStrategy>(
+    hash_map::Entry<'a, K, Stat<M, S>>,
+);
+
+impl<'a, K, const M: usize, S: RefCountStrategy> StatSheetEntry<'a, K, M, S> {
+    /// Inserts a stat with `base_value` if there is none, and returns the stat
+    pub fn or_insert(self, base_value: f32) -> &'a mut Stat<M, S> {
+        self.or_insert_with(|| Stat::new(base_value))
+    }
+
+    /// Inserts the stat returned from `f` if there is none, and returns the stat
+    pub fn or_insert_with(self, f: impl FnOnce() -> Stat<M, S>) -> &'a mut Stat<M, S> {
+        self.0.or_insert_with(f)
+    }
+
+    pub fn key(&self) -> &K {
+        self.0.key()
+    }
+}
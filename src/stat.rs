@@ -1,59 +1,243 @@
-use crate::modifier::StatModifier;
+use crate::builder::StatBuilder;
+use crate::modifier::{
+    Condition, ModifierDef, ModifierKind, ModifierSource, PackedModifier, StatModifier,
+};
+use crate::ref_count::{ArcStrategy, DefaultStrategy, RcStrategy, RefCountStrategy};
 #[cfg(feature = "reflect")]
 use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use std::hash::{Hash, Hasher};
 use tinyvec::{ArrayVec, TinyVec};
 
-// By default (single-threaded) implementation is most optimized by using std::rc
-// if one needs Stat to live in a multithreaded environment, enabling sync feature uses std::sync instead
-#[cfg(not(feature = "sync"))]
-type ReferenceCounted<T> = std::rc::Rc<T>;
-#[cfg(not(feature = "sync"))]
-type Weak<T> = std::rc::Weak<T>;
-#[cfg(not(feature = "sync"))]
-type InteriorCell<T> = std::cell::RefCell<T>;
-#[cfg(feature = "sync")]
-type ReferenceCounted<T> = std::sync::Arc<T>;
-#[cfg(feature = "sync")]
-type Weak<T> = std::sync::Weak<T>;
-#[cfg(feature = "sync")]
-type InteriorCell<T> = std::sync::Arc<std::sync::Mutex<T>>;
+/// A read only view of the value of a [`Stat`], returned from ```stat.value_cell()```.
+/// The stat keeps it up to date every time it recalculates, so readers don't need access to the stat itself
+#[derive(Clone, Debug, Default)]
+pub struct StatValueCell<S: RefCountStrategy = DefaultStrategy>(S::SharedF32);
 
-#[cfg(not(feature = "sync"))]
-#[inline]
-fn new_interior_cell<T>(value: T) -> InteriorCell<T> {
-    InteriorCell::new(value)
+impl<S: RefCountStrategy> StatValueCell<S> {
+    pub fn get(&self) -> f32 {
+        S::get_shared_f32(&self.0)
+    }
+
+    fn set(&self, value: f32) {
+        S::set_shared_f32(&self.0, value)
+    }
 }
 
-#[cfg(feature = "sync")]
-#[inline]
-fn new_interior_cell<T>(value: T) -> InteriorCell<T> {
-    std::sync::Arc::new(std::sync::Mutex::new(value))
+/// This handle is returned from calling ```stat.add_modifier()``` (technically it's returned in the Ok, result).
+///
+/// The handle controls the validity of a modifier.
+/// Once dropped, the modifier is automatically removed from the [`super::Stat`] that created it.
+pub type StatModifierHandle<S = DefaultStrategy> = <S as RefCountStrategy>::Handle;
+
+/// Returned from ```stat.try_add_modifier_with_order()``` when a modifier of a different kind already uses the order.
+/// The result would then depend on which modifier was added first
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct OrderCollisionError {
+    pub order: i32,
+    /// kind of the modifier already using the order
+    pub existing_kind: ModifierKind,
 }
 
-#[cfg(not(feature = "sync"))]
-type RefMut<'a, T> = std::cell::RefMut<'a, T>;
+impl std::fmt::Display for OrderCollisionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "order {} is already used by a modifier of kind {:?}",
+            self.order, self.existing_kind
+        )
+    }
+}
 
-#[cfg(feature = "sync")]
-type RefMut<'a, T> = std::sync::MutexGuard<'a, T>;
+impl std::error::Error for OrderCollisionError {}
 
-#[cfg(not(feature = "sync"))]
-#[inline]
-fn borrow_cell<T>(cell: &InteriorCell<T>) -> std::cell::RefMut<T> {
-    cell.borrow_mut()
+/// Returned from ```stat.clone_config_onto()``` and ```Stat::try_from_iter()``` when the target can't fit every modifier on the stack
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ModifiersFullError {
+    /// how many modifiers would have been added. ```Stat::try_from_iter()``` stops at the first one that doesn't fit,
+    /// so it only counts up to that one
+    pub needed: usize,
+    /// how many more modifiers the target fits on the stack
+    pub available: usize,
 }
 
-#[cfg(feature = "sync")]
-#[inline]
-fn borrow_cell<T>(cell: &InteriorCell<T>) -> std::sync::MutexGuard<T> {
-    // cell.try_lock().unwrap()
-    cell.lock().unwrap()
+impl std::fmt::Display for ModifiersFullError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} modifiers don't fit, only {} slots are left",
+            self.needed, self.available
+        )
+    }
 }
 
-/// This handle is returned from calling ```stat.add_modifier()``` (technically it's returned in the Ok, result).
-///
-/// The handle controls the validity of a modifier.
-/// Once dropped, the modifier is automatically removed from the [`super::Stat`] that created it.
-pub type StatModifierHandle = ReferenceCounted<StatModifierHandleTag>;
+impl std::error::Error for ModifiersFullError {}
+
+/// How the final value of a [`Stat`] is rounded, see [`Stat::set_rounding()`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "reflect", derive(Reflect))]
+pub enum Rounding {
+    /// rounds down, 4.7 becomes 4
+    Floor,
+    /// rounds up, 4.2 becomes 5
+    Ceil,
+    /// rounds to the nearest whole number, half way cases away from zero
+    Round,
+}
+
+impl Rounding {
+    pub fn apply(&self, value: f32) -> f32 {
+        match self {
+            Rounding::Floor => value.floor(),
+            Rounding::Ceil => value.ceil(),
+            Rounding::Round => value.round(),
+        }
+    }
+}
+
+/// How modifiers with the same order are sorted, see [`Stat::set_tie_break()`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "reflect", derive(Reflect))]
+pub enum TieBreak {
+    /// the modifier added first is applied first
+    #[default]
+    ByInsertion,
+    /// sorted by [`super::ModifierKind`] id, flats are applied before percents no matter when they were added
+    ByKind,
+}
+
+// the number modifiers are applied to while calculating, f64 with stat.set_f64_accumulation()
+trait Accumulator {
+    fn to_f32(&self) -> f32;
+    fn apply(&mut self, modifier: &StatModifier);
+    fn apply_rounding(&mut self, rounding: Rounding);
+    fn clamp_to(&mut self, min: Option<f32>, max: Option<f32>);
+}
+
+impl Accumulator for f32 {
+    fn to_f32(&self) -> f32 {
+        *self
+    }
+
+    fn apply(&mut self, modifier: &StatModifier) {
+        modifier.apply(self);
+    }
+
+    fn apply_rounding(&mut self, rounding: Rounding) {
+        *self = rounding.apply(*self);
+    }
+
+    fn clamp_to(&mut self, min: Option<f32>, max: Option<f32>) {
+        if let Some(min) = min {
+            *self = self.max(min);
+        }
+        if let Some(max) = max {
+            *self = self.min(max);
+        }
+    }
+}
+
+impl Accumulator for f64 {
+    fn to_f32(&self) -> f32 {
+        *self as f32
+    }
+
+    fn apply(&mut self, modifier: &StatModifier) {
+        modifier.apply_f64(self);
+    }
+
+    fn apply_rounding(&mut self, rounding: Rounding) {
+        *self = match rounding {
+            Rounding::Floor => self.floor(),
+            Rounding::Ceil => self.ceil(),
+            Rounding::Round => self.round(),
+        };
+    }
+
+    fn clamp_to(&mut self, min: Option<f32>, max: Option<f32>) {
+        if let Some(min) = min {
+            *self = self.max(min as f64);
+        }
+        if let Some(max) = max {
+            *self = self.min(max as f64);
+        }
+    }
+}
+
+/// Integer types the value of a [`Stat`] can be converted to with ```stat.value_as()```
+pub trait FromStatValue {
+    /// rounds to the nearest whole number, saturating at the bounds of the type. NaN becomes 0
+    fn from_stat_value(value: f32) -> Self;
+}
+
+macro_rules! impl_from_stat_value {
+    ($($integer:ty),*) => {
+        $(
+            impl FromStatValue for $integer {
+                fn from_stat_value(value: f32) -> Self {
+                    // float to integer casts saturate
+                    value.round() as $integer
+                }
+            }
+        )*
+    };
+}
+
+impl_from_stat_value!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// How many values a [`Stat`] remembers, see ```stat.history()```
+#[cfg(feature = "history")]
+pub const HISTORY_CAPACITY: usize = 64;
+
+// the latest read values, kept contiguous and oldest first so they can be handed out as a slice
+#[cfg(feature = "history")]
+#[derive(Clone, Debug)]
+struct History {
+    values: [f32; HISTORY_CAPACITY],
+    len: usize,
+    // the value changed since it was last read
+    pending: bool,
+}
+
+#[cfg(feature = "history")]
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            values: [0.0; HISTORY_CAPACITY],
+            len: 0,
+            pending: false,
+        }
+    }
+}
+
+#[cfg(feature = "history")]
+impl History {
+    fn push(&mut self, value: f32) {
+        if self.len == HISTORY_CAPACITY {
+            self.values.copy_within(1.., 0);
+            self.len -= 1;
+        }
+        self.values[self.len] = value;
+        self.len += 1;
+    }
+}
+
+/// The latest values of a [`Stat`] oldest first, derefs to a slice. Returned from ```stat.history()```,
+/// the stat can't be read while it's held
+#[cfg(feature = "history")]
+pub struct HistoryRef<'a, S: RefCountStrategy = DefaultStrategy>(S::CellGuard<'a, History>);
+
+#[cfg(feature = "history")]
+impl<S: RefCountStrategy> std::ops::Deref for HistoryRef<'_, S> {
+    type Target = [f32];
+
+    fn deref(&self) -> &[f32] {
+        &self.0.values[..self.0.len]
+    }
+}
 
 /// Just an empty 'flavor' struct, to indicate that the [`StatModifierHandle`] is an owner of some value
 #[derive(Copy, Clone, Debug, Default)]
@@ -63,71 +247,473 @@ pub struct StatModifierHandleTag;
 /// A value that can be modified through [`super::StatModifier`]
 ///
 /// ```const M: usize``` decides how many modifiers a stat can maximally hold (modifier are internally an array on the stack)
-#[derive(Clone, Debug)]
+///
+/// ```S``` decides how handles are reference counted, see [`RefCountStrategy`]. Leaving it out uses the [`DefaultStrategy`]
+#[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "reflect", derive(Reflect))]
 #[cfg_attr(feature = "reflect", reflect(Serialize, Deserialize))]
-pub struct Stat<const M: usize> {
+pub struct Stat<const M: usize, S: RefCountStrategy = DefaultStrategy> {
     pub base_value: f32,
     // calculated from base_value and modifiers
-    #[cfg_attr(feature = "serde", serde(skip, default = "default_value"))]
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_value::<S>"))]
+    #[cfg_attr(feature = "reflect", reflect(ignore))]
+    value: S::Cell<f32>,
+    // same as value, before the bounds are applied
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_value::<S>"))]
+    #[cfg_attr(feature = "reflect", reflect(ignore))]
+    raw_value: S::Cell<f32>,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "reflect", reflect(ignore))]
+    modifiers: S::Cell<TinyVec<[ModifierMeta<S>; M]>>,
+    // the value is outdated and is calculated on the next read
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_dirty::<S>"))]
+    #[cfg_attr(feature = "reflect", reflect(ignore))]
+    dirty: S::Cell<bool>,
+
+    // optional bounds the calculated value is clamped to
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) min_value: Option<f32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) max_value: Option<f32>,
+    // clamp every time the order changes, instead of only the final value
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) clamp_between_phases: bool,
+    // limits the value relative to the base value, value <= base_value * max_multiplier
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) min_multiplier: Option<f32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) max_multiplier: Option<f32>,
+    // rounds the value after the multiplier limits, before the bounds
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) rounding: Option<Rounding>,
+    // rounds after every single modifier
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) step_rounding: Option<Rounding>,
+    // secondary sort key for modifiers sharing an order
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) tie_break: TieBreak,
+    // applies the modifiers to an f64, only the result is stored as f32
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) f64_accumulation: bool,
+
+    // decides which conditional modifiers apply, like the health percentage for "while below 50% health"
+    #[cfg_attr(feature = "serde", serde(default))]
+    condition_value: Option<f32>,
+
+    // forced value, ignoring base value and modifiers
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) override_value: Option<f32>,
+    // overrides pushed through push_override(), the latest one with a valid handle wins
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "reflect", reflect(ignore))]
+    override_stack: S::Cell<Vec<(f32, S::WeakHandle)>>,
+
+    // shared with readers through value_cell()
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "reflect", reflect(ignore))]
+    value_cell: S::Cell<Option<StatValueCell<S>>>,
+
+    // last value returned by value_delta()
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "reflect", reflect(ignore))]
+    last_reported_value: Option<f32>,
+
+    // handed to the next added modifier, keeps modifiers sharing an order in the order they were added
+    #[cfg_attr(feature = "serde", serde(skip))]
     #[cfg_attr(feature = "reflect", reflect(ignore))]
-    value: InteriorCell<f32>,
+    next_sequence: u64,
 
+    // the latest read values, oldest first
+    #[cfg(feature = "history")]
     #[cfg_attr(feature = "serde", serde(skip))]
     #[cfg_attr(feature = "reflect", reflect(ignore))]
-    modifiers: InteriorCell<TinyVec<[ModifierMeta; M]>>,
+    history: S::Cell<History>,
+}
+
+/// A [`Stat`] with ```Rc``` handles, the cheaper choice for stats that stay on one thread
+pub type LocalStat<const M: usize> = Stat<M, RcStrategy>;
+
+/// A [`Stat`] with ```Arc``` handles, it can be sent to other threads along with its handles.
+/// Available without the **sync** feature, which only changes what a plain [`Stat`] uses
+pub type SyncStat<const M: usize> = Stat<M, ArcStrategy>;
+
+// the cells are copied instead of shared (with ArcStrategy they are an Arc),
+// a clone doesn't write to the value cell handed out by the original
+impl<const M: usize, S: RefCountStrategy> Clone for Stat<M, S> {
+    fn clone(&self) -> Self {
+        Self {
+            base_value: self.base_value,
+            value: S::clone_cell(&self.value),
+            raw_value: S::clone_cell(&self.raw_value),
+            modifiers: S::clone_cell(&self.modifiers),
+            dirty: S::clone_cell(&self.dirty),
+            min_value: self.min_value,
+            max_value: self.max_value,
+            clamp_between_phases: self.clamp_between_phases,
+            min_multiplier: self.min_multiplier,
+            max_multiplier: self.max_multiplier,
+            rounding: self.rounding,
+            step_rounding: self.step_rounding,
+            tie_break: self.tie_break,
+            f64_accumulation: self.f64_accumulation,
+            condition_value: self.condition_value,
+            override_value: self.override_value,
+            override_stack: S::clone_cell(&self.override_stack),
+            value_cell: S::new_cell(None),
+            last_reported_value: self.last_reported_value,
+            next_sequence: self.next_sequence,
+            #[cfg(feature = "history")]
+            history: S::clone_cell(&self.history),
+        }
+    }
 }
 
-impl<const M: usize> PartialEq for Stat<M> {
+impl<const M: usize, S: RefCountStrategy> PartialEq for Stat<M, S> {
     fn eq(&self, other: &Self) -> bool {
         self.base_value == other.base_value
     }
 }
 
 #[cfg(feature = "serde")]
-fn default_value() -> InteriorCell<f32> {
-    new_interior_cell(0.0f32)
+fn default_value<S: RefCountStrategy>() -> S::Cell<f32> {
+    S::new_cell(0.0f32)
+}
+
+#[cfg(feature = "serde")]
+fn default_dirty<S: RefCountStrategy>() -> S::Cell<bool> {
+    S::new_cell(true)
 }
 
 /// create a stat from i32 (Stat is always internally a f32)
-impl<const M: usize> From<i32> for Stat<M> {
+impl<const M: usize, S: RefCountStrategy> From<i32> for Stat<M, S> {
     fn from(value: i32) -> Self {
         Self::new(value as f32)
     }
 }
 
 /// create a stat from i32 (Stat is always internally a f32)
-impl<const M: usize> From<f32> for Stat<M> {
+impl<const M: usize, S: RefCountStrategy> From<f32> for Stat<M, S> {
     fn from(value: f32) -> Self {
         Self::new(value)
     }
 }
 
-#[derive(Clone, Debug)]
-struct ModifierMeta {
+/// Adds a permanent ```StatModifier::Flat(rhs)```, see ```stat.add_permanent_modifier()```.
+/// The base value is left alone
+/// ```
+/// # use game_stat::prelude::*;
+/// let mut armor: Stat<2> = Stat::new(10.0);
+/// armor += 5.0;
+/// assert_eq!(armor.value(), 15.0);
+/// assert_eq!(armor.base_value, 10.0);
+/// ```
+impl<const M: usize, S: RefCountStrategy> std::ops::AddAssign<f32> for Stat<M, S> {
+    fn add_assign(&mut self, rhs: f32) {
+        self.add_permanent_modifier(StatModifier::Flat(rhs));
+    }
+}
+
+/// Adds a permanent ```StatModifier::PercentMultiply(rhs)```, see ```stat.add_permanent_modifier()```.
+/// The base value is left alone
+impl<const M: usize, S: RefCountStrategy> std::ops::MulAssign<f32> for Stat<M, S> {
+    fn mul_assign(&mut self, rhs: f32) {
+        self.add_permanent_modifier(StatModifier::PercentMultiply(rhs));
+    }
+}
+
+/// Creates a [`Stat`] from a base value and a list of [`super::ModifierDef`], with the capacity matching the amount of definitions.
+/// Returns the stat along with the handles
+/// ```
+/// # use game_stat::prelude::*;
+/// # use game_stat::stat_from_defs;
+/// let (stat, _handles) = stat_from_defs!(10.0, [
+///     ModifierDef::from(StatModifier::Flat(5.0)),
+///     ModifierDef::new(StatModifier::PercentMultiply(2.0), 1),
+/// ]);
+/// let stat: Stat<2> = stat;
+/// assert_eq!(stat.value(), 30.0);
+/// ```
+#[macro_export]
+macro_rules! stat_from_defs {
+    ($base_value:expr, [$($def:expr),* $(,)?]) => {
+        $crate::Stat::from_def_array($base_value, [$($def),*])
+    };
+}
+
+/// Consumes the stat, yielding every valid modifier with its order
+impl<const M: usize, S: RefCountStrategy> IntoIterator for Stat<M, S> {
+    type Item = (StatModifier, i32);
+    type IntoIter = std::vec::IntoIter<(StatModifier, i32)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_modifier_defs()
+            .into_iter()
+            .map(|def| (def.modifier, def.order))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct ModifierMeta<S: RefCountStrategy> {
     modifier: StatModifier,
     order: i32,
-    owner_modifier_weak: Weak<StatModifierHandleTag>,
+    // when the modifier was added, the last sort key
+    sequence: u64,
+    owner_modifier_weak: S::WeakHandle,
+    // added through stage_modifier(), doesn't apply until it's committed
+    staged: bool,
+    // keeps the handle alive, so dropping it doesn't remove the modifier (manual-pruning and permanent modifiers)
+    #[allow(dead_code)]
+    owner_modifier_strong: Option<StatModifierHandle<S>>,
+    // None for plain modifiers, boxed so the rarely used metadata doesn't grow every inline slot
+    extras: Option<Box<ModifierExtras>>,
 }
 
-impl Default for ModifierMeta {
-    fn default() -> Self {
+// metadata only some modifiers have, see ModifierMeta::extras
+#[derive(Clone, Debug, Default)]
+struct ModifierExtras {
+    // uses left before the modifier is removed, None if it never runs out
+    charges: Option<u32>,
+    // time left before the modifier expires, None if it never expires
+    remaining_duration: Option<f32>,
+    // time left before the modifier starts applying, it doesn't apply until then
+    remaining_delay: Option<f32>,
+    // groups modifiers from the same source, like every stack of poison
+    tag: Option<u64>,
+    source: ModifierSource,
+    // only the strongest modifier of an exclusive group applies
+    group: Option<u16>,
+    // for debugging and tooltips, like "Sword of Fire"
+    name: Option<&'static str>,
+    // only applies while the condition value of the stat meets it
+    condition: Option<Condition>,
+}
+
+static NO_EXTRAS: ModifierExtras = ModifierExtras {
+    charges: None,
+    remaining_duration: None,
+    remaining_delay: None,
+    tag: None,
+    source: ModifierSource::Unspecified,
+    group: None,
+    name: None,
+    condition: None,
+};
+
+impl ModifierExtras {
+    fn boxed(self) -> Option<Box<Self>> {
+        Some(Box::new(self))
+    }
+}
+
+impl<S: RefCountStrategy> From<ModifierDef> for ModifierMeta<S> {
+    fn from(def: ModifierDef) -> Self {
+        let has_extras =
+            def.tag.is_some() || def.source != ModifierSource::Unspecified || def.name.is_some();
         Self {
-            modifier: StatModifier::default(),
-            order: 0,
-            owner_modifier_weak: Weak::default(),
+            modifier: def.modifier,
+            order: def.order,
+            extras: has_extras.then(|| {
+                Box::new(ModifierExtras {
+                    tag: def.tag,
+                    source: def.source,
+                    name: def.name,
+                    ..Default::default()
+                })
+            }),
+            ..Default::default()
         }
     }
 }
 
-impl<const M: usize> Default for Stat<M> {
+impl<S: RefCountStrategy> ModifierMeta<S> {
+    fn def(&self) -> ModifierDef {
+        let extras = self.extras();
+        ModifierDef {
+            modifier: self.modifier,
+            order: self.order,
+            tag: extras.tag,
+            source: extras.source,
+            name: extras.name,
+        }
+    }
+
+    fn extras(&self) -> &ModifierExtras {
+        self.extras.as_deref().unwrap_or(&NO_EXTRAS)
+    }
+
+    // false while the modifier is staged, waiting for its delay, or its condition isn't met by the condition value
+    fn is_active(&self, condition_value: Option<f32>) -> bool {
+        let extras = self.extras();
+        !self.staged
+            && extras.remaining_delay.is_none()
+            && extras.condition.is_none_or(|condition| {
+                condition_value.is_some_and(|condition_value| condition.is_met(condition_value))
+            })
+    }
+
+    fn is_owned_by(&self, handle: &StatModifierHandle<S>) -> bool {
+        std::ptr::eq(
+            S::weak_ptr(&self.owner_modifier_weak),
+            S::handle_ptr(handle),
+        )
+    }
+}
+
+/// Mutable access to the modifiers of a [`Stat`], returned from [`Stat::modifiers_mut()`].
+/// Marks the stat as outdated when dropped
+pub struct ModifiersMut<'a, const M: usize, S: RefCountStrategy = DefaultStrategy> {
+    modifiers: S::CellGuard<'a, TinyVec<[ModifierMeta<S>; M]>>,
+    dirty: &'a S::Cell<bool>,
+}
+
+impl<const M: usize, S: RefCountStrategy> ModifiersMut<'_, M, S> {
+    /// Iterates every valid modifier
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ModifierRefMut<'_, S>> {
+        self.modifiers
+            .iter_mut()
+            .filter(|modifier_meta| S::strong_count(&modifier_meta.owner_modifier_weak) > 0)
+            .map(|modifier_meta| ModifierRefMut { modifier_meta })
+    }
+}
+
+impl<const M: usize, S: RefCountStrategy> Drop for ModifiersMut<'_, M, S> {
+    fn drop(&mut self) {
+        *S::borrow_cell(self.dirty) = true;
+    }
+}
+
+/// Mutable access to the base value of a [`Stat`], returned from [`Stat::base_value_mut()`].
+/// Marks the stat as outdated when dropped, unlike writing to ```stat.base_value``` directly
+pub struct BaseValueGuard<'a, const M: usize, S: RefCountStrategy = DefaultStrategy> {
+    stat: &'a mut Stat<M, S>,
+}
+
+impl<const M: usize, S: RefCountStrategy> std::ops::Deref for BaseValueGuard<'_, M, S> {
+    type Target = f32;
+
+    fn deref(&self) -> &Self::Target {
+        &self.stat.base_value
+    }
+}
+
+impl<const M: usize, S: RefCountStrategy> std::ops::DerefMut for BaseValueGuard<'_, M, S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.stat.base_value
+    }
+}
+
+impl<const M: usize, S: RefCountStrategy> Drop for BaseValueGuard<'_, M, S> {
+    fn drop(&mut self) {
+        self.stat.mark_dirty();
+    }
+}
+
+/// A modifier that doesn't apply yet, returned from ```stat.stage_modifier()```.
+/// Hand it to ```stat.commit_staged()``` to apply it or ```stat.rollback_staged()``` to discard it.
+/// Like a [`StatModifierHandle`], dropping it removes the modifier, with **manual-pruning** as well until it's committed
+#[derive(Debug)]
+#[must_use = "dropping a staged modifier discards it"]
+pub struct StagedHandle<S: RefCountStrategy = DefaultStrategy>(StatModifierHandle<S>);
+
+/// A copy of a modifier in a [`Stat`], yielded from ```stat.modifiers()```
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct ModifierInfo {
+    pub modifier: StatModifier,
+    pub order: i32,
+    /// set through ```stat.add_modifier_with_name()```
+    pub name: Option<&'static str>,
+}
+
+/// One applied modifier in the trace returned from ```stat.compute_trace()```
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct TraceStep {
+    pub order: i32,
+    /// increased modifiers of the same order are added up, they show up as a single step
+    pub kind: ModifierKind,
+    pub value_before: f32,
+    pub value_after: f32,
+}
+
+/// A single modifier yielded from [`ModifiersMut::iter_mut()`], only the order can be changed
+pub struct ModifierRefMut<'a, S: RefCountStrategy = DefaultStrategy> {
+    modifier_meta: &'a mut ModifierMeta<S>,
+}
+
+impl<S: RefCountStrategy> ModifierRefMut<'_, S> {
+    pub fn modifier(&self) -> &StatModifier {
+        &self.modifier_meta.modifier
+    }
+
+    pub fn order(&self) -> i32 {
+        self.modifier_meta.order
+    }
+
+    pub fn set_order(&mut self, order: i32) {
+        self.modifier_meta.order = order;
+    }
+}
+
+/// A calculation paused at an order, returned from ```stat.split_compute()```.
+/// Holds the value after the modifiers below the cutoff, the rest is applied by [`PartialComputation::finish()`]
+#[derive(Debug)]
+pub struct PartialComputation<'a, const M: usize, S: RefCountStrategy = DefaultStrategy> {
+    stat: &'a Stat<M, S>,
+    cutoff_order: i32,
+    value: f32,
+}
+
+impl<const M: usize, S: RefCountStrategy> PartialComputation<'_, M, S> {
+    /// Returns the value after every modifier below the cutoff order
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Applies the modifiers at or above the cutoff order to `value` (usually ```partial.value()``` with some changes),
+    /// followed by the multiplier limits, bounds and override just like ```stat.value()```
+    /// panics if refcell is borrowed
+    pub fn finish(&self, value: f32) -> f32 {
+        let stat = self.stat;
+        let mut value = value;
+        let mut modifiers = S::borrow_cell(&stat.modifiers);
+        stat.order_modifiers(&mut modifiers);
+        let split =
+            modifiers.partition_point(|modifier_meta| modifier_meta.order < self.cutoff_order);
+        // the phase changes at the cutoff
+        if stat.clamp_between_phases && split > 0 && split < modifiers.len() {
+            stat.clamp(&mut value);
+        }
+        stat.apply_modifier_range(
+            &modifiers,
+            split..modifiers.len(),
+            stat.condition_value,
+            &mut value,
+            None,
+        );
+        stat.limit_value(stat.base_value, &mut value);
+        stat.effective_override().unwrap_or(value)
+    }
+}
+
+impl<const M: usize, S: RefCountStrategy> Default for Stat<M, S> {
     fn default() -> Self {
         Self::new(0.0)
     }
 }
 
-impl<const M: usize> Stat<M> {
+impl<const M: usize, S: RefCountStrategy> Stat<M, S> {
+    /// How many modifiers the stat holds on the stack, usable in const contexts
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let tooltip_lines: [&str; Stat::<4>::CAP] = [""; 4];
+    /// ```
+    pub const CAP: usize = M;
+
     /// ```
     /// // EXAMPLE: Creates a stat that can hold a maximum of 3 modifiers
     /// # use game_stat::prelude::*;
@@ -135,89 +721,1523 @@ impl<const M: usize> Stat<M> {
     /// let attack_stat = Stat::<3>::new(0.0);
     /// ```
     pub fn new(base_value: f32) -> Self {
-        let modifiers = TinyVec::Inline(ArrayVec::<[ModifierMeta; M]>::default());
+        let modifiers = TinyVec::Inline(ArrayVec::<[ModifierMeta<S>; M]>::default());
         Self {
             base_value,
-            value: new_interior_cell(base_value),
-            modifiers: new_interior_cell(modifiers),
+            value: S::new_cell(base_value),
+            raw_value: S::new_cell(base_value),
+            modifiers: S::new_cell(modifiers),
+            dirty: S::new_cell(false),
+            min_value: None,
+            max_value: None,
+            clamp_between_phases: false,
+            min_multiplier: None,
+            max_multiplier: None,
+            rounding: None,
+            step_rounding: None,
+            tie_break: TieBreak::ByInsertion,
+            f64_accumulation: false,
+            condition_value: None,
+            override_value: None,
+            override_stack: S::new_cell(Vec::new()),
+            value_cell: S::new_cell(None),
+            last_reported_value: None,
+            next_sequence: 0,
+            #[cfg(feature = "history")]
+            history: S::new_cell(History::default()),
         }
     }
 
-    /// Add a modifier using the default order. [`super::StatModifier::default_order()`]
+    /// Creates a stat with modifiers already added (using their default order), returning the handles in the same order.
+    /// Providing more modifiers than the stat can hold on the stack is a compile error.
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let (armor, _handles) = Stat::<4>::new_with_modifiers(10.0, [StatModifier::Flat(5.0), StatModifier::PercentMultiply(2.0)]);
+    /// assert_eq!(armor.value(), 30.0);
+    /// ```
+    /// ```compile_fail
+    /// # use game_stat::prelude::*;
+    /// let (armor, _handles) = Stat::<1>::new_with_modifiers(10.0, [StatModifier::Flat(5.0), StatModifier::Flat(5.0)]);
+    /// ```
     /// panics if refcell is borrowed
-    pub fn add_modifier(&mut self, modifier: StatModifier) -> StatModifierHandle {
-        // We have to update the modifiers array in case one has been dropped.
-        // The modifier array could be full of data, yet have modifiers that aren't valid.
-        // If we drop a modifier and then add one right away, there should be space for it to be added.
-        // This ensures the array is up to date.
-        self.update_modifiers();
+    pub fn new_with_modifiers<const N: usize>(
+        base_value: f32,
+        modifiers: [StatModifier; N],
+    ) -> (Self, [StatModifierHandle<S>; N]) {
+        const { assert!(N <= M, "more modifiers than the stat can hold") };
+        let mut stat = Self::new(base_value);
+        let handles = modifiers.map(|modifier| stat.add_modifier(modifier));
+        (stat, handles)
+    }
 
-        let handle = ReferenceCounted::new(StatModifierHandleTag);
-        let meta = ModifierMeta {
-            order: modifier.default_order(),
-            modifier,
-            owner_modifier_weak: ReferenceCounted::downgrade(&handle),
-        };
+    /// Clamps the calculated value between `min` and `max`, `None` leaves that side unbounded
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut health: Stat<2> = Stat::new(90.0);
+    /// health.set_bounds(Some(0.0), Some(100.0));
+    /// let _potion = health.add_modifier(StatModifier::Flat(50.0));
+    /// assert_eq!(health.value(), 100.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn set_bounds(&mut self, min: Option<f32>, max: Option<f32>) {
+        self.min_value = min;
+        self.max_value = max;
+        self.calculate_internal_value();
+    }
 
-        let mut modifiers = borrow_cell(&self.modifiers);
-        if modifiers.len() + 1 > modifiers.capacity() {
-            modifiers.move_to_the_heap();
-        }
-        modifiers.push(meta);
-        drop(modifiers);
+    /// Returns the (min, max) bounds set through [`Stat::set_bounds()`]
+    pub fn bounds(&self) -> (Option<f32>, Option<f32>) {
+        (self.min_value, self.max_value)
+    }
 
+    /// When enabled, the bounds are applied every time the modifier order changes while calculating
+    /// (all modifiers of order 0, clamp, all modifiers of order 1, clamp...) instead of only to the final value.
+    /// A stack that overshoots the bounds midway will give a different result.
+    /// panics if refcell is borrowed
+    pub fn set_clamp_between_phases(&mut self, clamp_between_phases: bool) {
+        self.clamp_between_phases = clamp_between_phases;
         self.calculate_internal_value();
-        handle
     }
 
+    /// Forces the value to `Some(value)`, no matter the base value, modifiers or bounds (think god mode).
+    /// The modifiers are kept, `None` goes back to calculating the value from them
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut health: Stat<2> = Stat::new(100.0);
+    /// health.set_override(Some(9999.0));
+    /// assert_eq!(health.value(), 9999.0);
+    /// health.set_override(None);
+    /// assert_eq!(health.value(), 100.0);
+    /// ```
     /// panics if refcell is borrowed
-    pub fn add_modifier_with_order(
-        &mut self,
-        modifier: StatModifier,
-        order: i32,
-    ) -> StatModifierHandle {
-        // We have to update the modifiers array in case one has been dropped.
-        // The modifier array could be full of data, yet have modifiers that aren't valid.
-        // If we drop a modifier and then add one right away, there should be space for it to be added.
-        // This ensures the array is up to date.
-        self.update_modifiers();
-        let handle = ReferenceCounted::new(StatModifierHandleTag);
-        let modifier_meta = ModifierMeta {
-            modifier,
-            owner_modifier_weak: ReferenceCounted::downgrade(&handle),
-            order,
-        };
+    pub fn set_override(&mut self, value: Option<f32>) {
+        self.override_value = value;
+        self.calculate_internal_value();
+    }
 
-        let mut modifiers = borrow_cell(&self.modifiers);
-        if modifiers.len() + 1 > modifiers.capacity() {
-            modifiers.move_to_the_heap();
-        }
-        modifiers.push(modifier_meta);
-        drop(modifiers);
+    /// Returns the value set through [`Stat::set_override()`]
+    pub fn override_value(&self) -> Option<f32> {
+        self.override_value
+    }
 
-        // value needs to update
+    /// Forces the value to `value` for as long as the returned handle exists, like [`Stat::set_override()`].
+    /// Overrides stack up, the latest pushed one wins and dropping its handle reveals the one beneath.
+    /// Once all handles are gone the value set through ```stat.set_override()``` applies again, or the calculated value
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut speed: Stat<2> = Stat::new(10.0);
+    /// let _slowed = speed.push_override(2.0);
+    /// let frozen = speed.push_override(0.0);
+    /// assert_eq!(speed.value(), 0.0);
+    /// drop(frozen);
+    /// assert_eq!(speed.value(), 2.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn push_override(&mut self, value: f32) -> StatModifierHandle<S> {
+        let handle = S::new_handle();
+        S::borrow_cell(&self.override_stack).push((value, S::downgrade(&handle)));
         self.calculate_internal_value();
         handle
     }
 
-    // check if any modifiers have been dropped, and update the value + array
-    /// panics if refcell is borrowed
-    fn update_modifiers(&self) {
-        let mut modifiers = borrow_cell(&self.modifiers);
-        let mut any_modifier_dropped = false;
-
-        modifiers.retain(|m| {
-            let retain = m.owner_modifier_weak.upgrade().is_some();
-            if !retain {
-                any_modifier_dropped = true;
-            }
-            retain
-        });
-        drop(modifiers);
+    // the latest pushed override that's still valid, otherwise the one from set_override()
+    fn effective_override(&self) -> Option<f32> {
+        S::borrow_cell(&self.override_stack)
+            .iter()
+            .rev()
+            .find(|(_, owner_weak)| S::strong_count(owner_weak) > 0)
+            .map(|(value, _)| *value)
+            .or(self.override_value)
+    }
 
-        if any_modifier_dropped {
-            self.calculate_internal_value();
-        }
+    /// Limits the value to at most `max_multiplier` times the base value, no matter how many modifiers stack up.
+    /// Applied after all modifiers, before the bounds. `None` removes the limit.
+    ///
+    /// With a negative base value the limit is on the magnitude instead:
+    /// a base of -10 with a max multiplier of 2 keeps the value from going below -20
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut attack: Stat<2> = Stat::new(10.0);
+    /// attack.set_max_multiplier(Some(2.0));
+    /// let _handle = attack.add_modifier(StatModifier::PercentMultiply(5.0));
+    /// assert_eq!(attack.value(), 20.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn set_max_multiplier(&mut self, max_multiplier: Option<f32>) {
+        self.max_multiplier = max_multiplier;
+        self.calculate_internal_value();
+    }
+
+    /// Keeps the value at least `min_multiplier` times the base value, see [`Stat::set_max_multiplier()`]
+    /// panics if refcell is borrowed
+    pub fn set_min_multiplier(&mut self, min_multiplier: Option<f32>) {
+        self.min_multiplier = min_multiplier;
+        self.calculate_internal_value();
+    }
+
+    /// Returns the (min, max) multipliers set through [`Stat::set_min_multiplier()`] and [`Stat::set_max_multiplier()`]
+    pub fn multiplier_limits(&self) -> (Option<f32>, Option<f32>) {
+        (self.min_multiplier, self.max_multiplier)
+    }
+
+    /// Rounds the calculated value, useful when a stat is displayed or used as a whole number (like health points).
+    /// Applied after the multiplier limits, before the bounds. `None` keeps the value as is
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// # use game_stat::Rounding;
+    /// let mut health: Stat<2> = Stat::new(10.0);
+    /// health.set_rounding(Some(Rounding::Floor));
+    /// let _handle = health.add_modifier(StatModifier::PercentMultiply(1.15));
+    /// assert_eq!(health.value(), 11.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn set_rounding(&mut self, rounding: Option<Rounding>) {
+        self.rounding = rounding;
+        self.calculate_internal_value();
+    }
+
+    /// Returns the rounding set through [`Stat::set_rounding()`]
+    pub fn rounding(&self) -> Option<Rounding> {
+        self.rounding
+    }
+
+    /// Rounds the value after every modifier, instead of only the final value like [`Stat::set_rounding()`].
+    /// Keeps integer stats whole during the calculation, which can give a different result than rounding at the end:
+    /// 5 * 1.5 * 1.5 is 11.25, but flooring after each step gives 7 and then 10
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// # use game_stat::Rounding;
+    /// let mut damage: Stat<2> = Stat::new(5.0);
+    /// let _percent_1 = damage.add_modifier_with_order(StatModifier::PercentAdd(0.5), 0);
+    /// let _percent_2 = damage.add_modifier_with_order(StatModifier::PercentAdd(0.5), 1);
+    /// damage.set_rounding(Some(Rounding::Floor));
+    /// assert_eq!(damage.value(), 11.0);
+    /// damage.set_step_rounding(Some(Rounding::Floor));
+    /// assert_eq!(damage.value(), 10.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn set_step_rounding(&mut self, step_rounding: Option<Rounding>) {
+        self.step_rounding = step_rounding;
+        self.calculate_internal_value();
+    }
+
+    /// Returns the rounding set through [`Stat::set_step_rounding()`]
+    pub fn step_rounding(&self) -> Option<Rounding> {
+        self.step_rounding
+    }
+
+    /// Decides how modifiers with the same order are sorted, by default the modifier added first is applied first
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// # use game_stat::TieBreak;
+    /// let mut damage: Stat<2> = Stat::new(10.0);
+    /// let _double = damage.add_modifier_with_order(StatModifier::PercentMultiply(2.0), 0);
+    /// let _flat = damage.add_modifier_with_order(StatModifier::Flat(5.0), 0);
+    /// assert_eq!(damage.value(), 25.0);
+    /// damage.set_tie_break(TieBreak::ByKind);
+    /// assert_eq!(damage.value(), 30.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn set_tie_break(&mut self, tie_break: TieBreak) {
+        self.tie_break = tie_break;
+        self.calculate_internal_value();
+    }
+
+    /// Returns the policy set through [`Stat::set_tie_break()`]
+    pub fn tie_break(&self) -> TieBreak {
+        self.tie_break
+    }
+
+    /// When enabled, the modifiers are applied to an `f64` and only the result is stored as `f32`.
+    /// Long chains of percent modifiers lose less precision along the way, at a small cost per calculation
+    /// panics if refcell is borrowed
+    pub fn set_f64_accumulation(&mut self, f64_accumulation: bool) {
+        self.f64_accumulation = f64_accumulation;
+        self.calculate_internal_value();
+    }
+
+    /// Returns the setting set through [`Stat::set_f64_accumulation()`]
+    pub fn f64_accumulation(&self) -> bool {
+        self.f64_accumulation
+    }
+
+    /// Starts configuring a stat, see [`StatBuilder`]
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// # use game_stat::Rounding;
+    /// let health = Stat::<4>::builder()
+    ///     .base(10.0)
+    ///     .bounds(0.0, 100.0)
+    ///     .rounding(Rounding::Floor)
+    ///     .build();
+    /// assert_eq!(health.value(), 10.0);
+    /// ```
+    pub fn builder() -> StatBuilder<M, S> {
+        StatBuilder::new()
+    }
+
+    // copies everything that's not the base value or modifiers
+    fn copy_config<const N: usize>(&mut self, other: &Stat<N, S>) {
+        self.min_value = other.min_value;
+        self.max_value = other.max_value;
+        self.clamp_between_phases = other.clamp_between_phases;
+        self.min_multiplier = other.min_multiplier;
+        self.max_multiplier = other.max_multiplier;
+        self.rounding = other.rounding;
+        self.step_rounding = other.step_rounding;
+        self.tie_break = other.tie_break;
+        self.f64_accumulation = other.f64_accumulation;
+        self.condition_value = other.condition_value;
+        self.override_value = other.override_value;
+    }
+
+    /// Creates a stat and adds every definition to it, returning the fresh handles in the same order as `defs`
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let (stat, _handles) = Stat::<2>::from_base_and_defs(10.0, [ModifierDef::from(StatModifier::Flat(5.0))]);
+    /// assert_eq!(stat.value(), 15.0);
+    /// ```
+    pub fn from_base_and_defs(
+        base_value: f32,
+        defs: impl IntoIterator<Item = ModifierDef>,
+    ) -> (Self, Vec<StatModifierHandle<S>>) {
+        let mut stat = Self::new(base_value);
+        let handles = defs
+            .into_iter()
+            .map(|def| stat.add_modifier_meta(def.into()))
+            .collect();
+        (stat, handles)
+    }
+
+    /// Like [`Stat::from_base_and_defs()`], but makes sure every modifier fits on the stack.
+    /// Stops consuming `defs` and fails as soon as there are more than `M` definitions
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let defs = [StatModifier::Flat(5.0), StatModifier::Flat(1.0)].map(ModifierDef::from);
+    /// assert!(Stat::<2>::try_from_iter(10.0, defs).is_ok());
+    /// assert!(Stat::<1>::try_from_iter(10.0, defs).is_err());
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn try_from_iter(
+        base_value: f32,
+        defs: impl IntoIterator<Item = ModifierDef>,
+    ) -> Result<(Self, Vec<StatModifierHandle<S>>), ModifiersFullError> {
+        let mut stat = Self::new(base_value);
+        let mut handles = Vec::with_capacity(M);
+        for def in defs {
+            if handles.len() == M {
+                return Err(ModifiersFullError {
+                    needed: M + 1,
+                    available: M,
+                });
+            }
+            handles.push(stat.add_modifier_meta(def.into()));
+        }
+        Ok((stat, handles))
+    }
+
+    /// Creates a stat holding exactly as many modifiers on the stack as there are definitions.
+    /// See [`stat_from_defs!`](crate::stat_from_defs) for a shorthand
+    /// panics if refcell is borrowed
+    pub fn from_def_array(
+        base_value: f32,
+        defs: [ModifierDef; M],
+    ) -> (Self, [StatModifierHandle<S>; M]) {
+        let mut stat = Self::new(base_value);
+        let handles = defs.map(|def| stat.add_modifier_meta(def.into()));
+        (stat, handles)
+    }
+
+    /// Returns the base value and every valid modifier as `(kind, value, order)`, where kind is a [`super::ModifierKind`] id.
+    /// A plain representation for passing stats over FFI (to a scripting language for example), see [`Stat::from_raw()`]
+    /// panics if refcell is borrowed
+    pub fn to_raw(&self) -> (f32, Vec<(u8, f32, i32)>) {
+        self.update_modifiers();
+        let modifiers = S::borrow_cell(&self.modifiers);
+        let raw_modifiers = modifiers
+            .iter()
+            .map(|modifier_meta| {
+                (
+                    modifier_meta.modifier.kind() as u8,
+                    modifier_meta.modifier.value(),
+                    modifier_meta.order,
+                )
+            })
+            .collect();
+        (self.base_value, raw_modifiers)
+    }
+
+    /// Creates a stat from the representation returned by [`Stat::to_raw()`], along with the handles of the modifiers.
+    /// Returns None if a kind id is unknown
+    /// panics if refcell is borrowed
+    pub fn from_raw(
+        base_value: f32,
+        raw_modifiers: &[(u8, f32, i32)],
+    ) -> Option<(Self, Vec<StatModifierHandle<S>>)> {
+        let defs = raw_modifiers
+            .iter()
+            .map(|&(kind, value, order)| {
+                let kind = ModifierKind::try_from(kind).ok()?;
+                Some(ModifierDef::new(
+                    StatModifier::from_kind(kind, value),
+                    order,
+                ))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self::from_base_and_defs(base_value, defs))
+    }
+
+    /// Packs every valid modifier into 8 bytes, a compact format for network replication.
+    /// Orders aren't packed, ```stat.apply_packed()``` adds the modifiers back using their default order
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut server: Stat<2> = Stat::new(10.0);
+    /// let _sword = server.add_modifier(StatModifier::Flat(5.0));
+    /// let mut client: Stat<2> = Stat::new(10.0);
+    /// let _handles = client.apply_packed(&server.pack_modifiers()).unwrap();
+    /// assert_eq!(client.value(), 15.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn pack_modifiers(&self) -> Vec<PackedModifier> {
+        self.modifiers()
+            .map(|info| PackedModifier::from(info.modifier))
+            .collect()
+    }
+
+    /// Adds every packed modifier using the default order, returning the handles in the same order.
+    /// Returns None without adding anything if a kind id is unknown
+    /// panics if refcell is borrowed
+    pub fn apply_packed(
+        &mut self,
+        packed: &[PackedModifier],
+    ) -> Option<Vec<StatModifierHandle<S>>> {
+        let modifiers = packed
+            .iter()
+            .map(|&packed| StatModifier::try_from(packed).ok())
+            .collect::<Option<Vec<_>>>()?;
+        Some(
+            modifiers
+                .into_iter()
+                .map(|modifier| self.add_modifier(modifier))
+                .collect(),
+        )
+    }
+
+    /// Creates a new stat with the same base value and bounds, where every valid modifier is replaced by `f(modifier)`.
+    /// Orders are kept, self is left untouched. Returns the new stat along with the handles of its modifiers
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut stat: Stat<2> = Stat::new(10.0);
+    /// let _flat = stat.add_modifier(StatModifier::Flat(5.0));
+    /// let (doubled, _handles) = stat.map_modifiers(|modifier| match modifier {
+    ///     StatModifier::Flat(v) => StatModifier::Flat(v * 2.0),
+    ///     other => *other,
+    /// });
+    /// assert_eq!(doubled.value(), 20.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn map_modifiers(
+        &self,
+        f: impl Fn(&StatModifier) -> StatModifier,
+    ) -> (Self, Vec<StatModifierHandle<S>>) {
+        self.update_modifiers();
+        let defs: Vec<ModifierDef> = S::borrow_cell(&self.modifiers)
+            .iter()
+            .map(|modifier_meta| ModifierDef {
+                modifier: f(&modifier_meta.modifier),
+                ..modifier_meta.def()
+            })
+            .collect();
+
+        let mut stat = Self::new(self.base_value);
+        stat.copy_config(self);
+        let handles = defs
+            .into_iter()
+            .map(|def| stat.add_modifier_meta(def.into()))
+            .collect();
+        (stat, handles)
+    }
+
+    /// Consumes the stat and returns a definition for every modifier that is still valid, keeping their tag, source and name.
+    /// Pair it with [`Stat::from_base_and_defs()`] to move a set of modifiers onto another stat.
+    /// panics if refcell is borrowed
+    pub fn into_modifier_defs(self) -> Vec<ModifierDef> {
+        self.update_modifiers();
+        let modifiers = S::borrow_cell(&self.modifiers);
+        modifiers.iter().map(ModifierMeta::def).collect()
+    }
+
+    /// Moves every valid modifier into a stat that holds `N` modifiers on the stack, the existing handles keep controlling them.
+    /// Returns the original stat untouched if it currently holds more valid modifiers than `N`
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut stat: Stat<16> = Stat::new(10.0);
+    /// let _handle = stat.add_modifier(StatModifier::Flat(5.0));
+    /// let stat: Stat<4> = stat.into_capacity().unwrap();
+    /// assert_eq!(stat.value(), 15.0);
+    /// ```
+    /// panics if refcell is borrowed
+    // handing the stat back is the whole point of the error
+    #[allow(clippy::result_large_err)]
+    pub fn into_capacity<const N: usize>(self) -> Result<Stat<N, S>, Self> {
+        self.update_modifiers();
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        if modifiers.len() > N {
+            drop(modifiers);
+            return Err(self);
+        }
+
+        let stat = Stat::<N, S> {
+            base_value: self.base_value,
+            value: S::new_cell(self.base_value),
+            raw_value: S::new_cell(self.base_value),
+            modifiers: S::new_cell(modifiers.drain(..).collect()),
+            dirty: S::new_cell(false),
+            min_value: self.min_value,
+            max_value: self.max_value,
+            clamp_between_phases: self.clamp_between_phases,
+            min_multiplier: self.min_multiplier,
+            max_multiplier: self.max_multiplier,
+            rounding: self.rounding,
+            step_rounding: self.step_rounding,
+            tie_break: self.tie_break,
+            f64_accumulation: self.f64_accumulation,
+            condition_value: self.condition_value,
+            override_value: self.override_value,
+            override_stack: S::new_cell(std::mem::take(&mut *S::borrow_cell(&self.override_stack))),
+            value_cell: S::new_cell(S::borrow_cell(&self.value_cell).take()),
+            last_reported_value: self.last_reported_value,
+            next_sequence: self.next_sequence,
+            #[cfg(feature = "history")]
+            history: S::new_cell(std::mem::take(&mut *S::borrow_cell(&self.history))),
+        };
+        stat.calculate_internal_value();
+        Ok(stat)
+    }
+
+    /// Add a modifier using the default order. [`super::StatModifier::default_order()`]
+    /// panics if refcell is borrowed
+    pub fn add_modifier(&mut self, modifier: StatModifier) -> StatModifierHandle<S> {
+        self.add_modifier_with_order(modifier, modifier.default_order())
+    }
+
+    /// panics if refcell is borrowed
+    pub fn add_modifier_with_order(
+        &mut self,
+        modifier: StatModifier,
+        order: i32,
+    ) -> StatModifierHandle<S> {
+        self.add_modifier_meta(ModifierMeta {
+            modifier,
+            order,
+            ..Default::default()
+        })
+    }
+
+    /// Strict version of [`Stat::add_modifier_with_order()`], fails if a modifier of a different kind already uses `order`.
+    /// Modifiers of different kinds don't commute (flat then percent isn't percent then flat),
+    /// so sharing an order leaves the result up to the order they were added in
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut stat: Stat<2> = Stat::new(10.0);
+    /// let _flat = stat.try_add_modifier_with_order(StatModifier::Flat(5.0), 0).unwrap();
+    /// assert!(stat.try_add_modifier_with_order(StatModifier::PercentAdd(0.5), 0).is_err());
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn try_add_modifier_with_order(
+        &mut self,
+        modifier: StatModifier,
+        order: i32,
+    ) -> Result<StatModifierHandle<S>, OrderCollisionError> {
+        self.update_modifiers();
+        let modifiers = S::borrow_cell(&self.modifiers);
+        let collision = modifiers.iter().find(|modifier_meta| {
+            modifier_meta.order == order && modifier_meta.modifier.kind() != modifier.kind()
+        });
+        if let Some(modifier_meta) = collision {
+            return Err(OrderCollisionError {
+                order,
+                existing_kind: modifier_meta.modifier.kind(),
+            });
+        }
+        drop(modifiers);
+
+        Ok(self.add_modifier_with_order(modifier, order))
+    }
+
+    /// Add a modifier using the default order, controlled by an existing handle instead of a new one.
+    /// One handle can control modifiers on several stats, dropping it removes all of them
+    /// ```
+    /// # #[cfg(not(feature = "manual-pruning"))]
+    /// # {
+    /// # use game_stat::prelude::*;
+    /// let mut attack: Stat<2> = Stat::new(10.0);
+    /// let mut defense: Stat<2> = Stat::new(10.0);
+    /// let berserk = attack.add_modifier(StatModifier::Flat(5.0));
+    /// defense.attach_modifier(&berserk, StatModifier::Flat(-5.0));
+    /// assert_eq!((attack.value(), defense.value()), (15.0, 5.0));
+    /// drop(berserk);
+    /// assert_eq!((attack.value(), defense.value()), (10.0, 10.0));
+    /// # }
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn attach_modifier(&mut self, handle: &StatModifierHandle<S>, modifier: StatModifier) {
+        self.attach_modifier_with_order(handle, modifier, modifier.default_order());
+    }
+
+    /// Same as [`Stat::attach_modifier()`] with a custom order
+    /// panics if refcell is borrowed
+    pub fn attach_modifier_with_order(
+        &mut self,
+        handle: &StatModifierHandle<S>,
+        modifier: StatModifier,
+        order: i32,
+    ) {
+        self.attach_modifier_meta(
+            handle,
+            ModifierMeta {
+                modifier,
+                order,
+                ..Default::default()
+            },
+        );
+    }
+
+    /// Add a modifier using the default order, that belongs to the stat itself instead of a handle.
+    /// It stays for as long as the stat exists, only ```stat.remove_by_source()``` can remove it
+    /// panics if refcell is borrowed
+    pub fn add_permanent_modifier(&mut self, modifier: StatModifier) {
+        self.add_permanent_modifier_with_order(modifier, modifier.default_order());
+    }
+
+    /// see [`Stat::add_permanent_modifier()`]
+    /// panics if refcell is borrowed
+    pub fn add_permanent_modifier_with_order(&mut self, modifier: StatModifier, order: i32) {
+        let handle = S::new_handle();
+        self.attach_modifier_meta(
+            &handle,
+            ModifierMeta {
+                modifier,
+                order,
+                owner_modifier_strong: Some(handle.clone()),
+                ..Default::default()
+            },
+        );
+    }
+
+    /// Adds a permanent ```StatModifier::PercentAdd``` with a value picked from `range`, so every spawned NPC ends up a bit different.
+    /// Returns the rolled modifier. A seeded `rng` gives the same jitter every time
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut health: Stat<2> = Stat::new(100.0);
+    /// health.with_jitter(-0.1..=0.1, &mut rand::thread_rng());
+    /// assert!((90.0..=110.0).contains(&health.value()));
+    /// ```
+    /// panics if refcell is borrowed
+    #[cfg(feature = "rand")]
+    pub fn with_jitter<R: rand::Rng + ?Sized>(
+        &mut self,
+        range: impl rand::distributions::uniform::SampleRange<f32>,
+        rng: &mut R,
+    ) -> StatModifier {
+        let modifier = StatModifier::roll_percent(range, rng);
+        self.add_permanent_modifier(modifier);
+        modifier
+    }
+
+    /// Add a modifier using the default order, also returns true if the modifier took the place of a modifier
+    /// whose handle was dropped, but wasn't cleaned up yet. Useful for transition animations
+    /// panics if refcell is borrowed
+    pub fn add_modifier_reporting_reuse(
+        &mut self,
+        modifier: StatModifier,
+    ) -> (StatModifierHandle<S>, bool) {
+        let reused = self.update_modifiers() > 0;
+        (self.add_modifier(modifier), reused)
+    }
+
+    /// Add a modifier using the default order, also returns whether the value changed because of it.
+    /// A modifier like ```StatModifier::Flat(0.0)``` returns false, so redrawing a tooltip can be skipped
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut armor: Stat<2> = Stat::new(10.0);
+    /// let (_handle, changed) = armor.checked_add_modifier(StatModifier::Flat(0.0));
+    /// assert!(!changed);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn checked_add_modifier(
+        &mut self,
+        modifier: StatModifier,
+    ) -> (StatModifierHandle<S>, bool) {
+        let previous_value = self.value();
+        let handle = self.add_modifier(modifier);
+        let changed = self.value().to_bits() != previous_value.to_bits();
+        (handle, changed)
+    }
+
+    /// Add a modifier using the default order, that is removed once ```stat.consume_charge()``` has been called `charges` times.
+    /// Returns None without adding anything if `charges` is 0
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut attack: Stat<2> = Stat::new(10.0);
+    /// let next_attack = attack.add_modifier_with_charges(StatModifier::PercentAdd(0.5), 1).unwrap();
+    /// assert_eq!(attack.value(), 15.0);
+    /// attack.consume_charge(&next_attack);
+    /// assert_eq!(attack.value(), 10.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn add_modifier_with_charges(
+        &mut self,
+        modifier: StatModifier,
+        charges: u32,
+    ) -> Option<StatModifierHandle<S>> {
+        if charges == 0 {
+            return None;
+        }
+        Some(
+            self.add_modifier_meta(ModifierMeta {
+                order: modifier.default_order(),
+                modifier,
+                extras: ModifierExtras {
+                    charges: Some(charges),
+                    ..Default::default()
+                }
+                .boxed(),
+                ..Default::default()
+            }),
+        )
+    }
+
+    /// Add a modifier using the default order, that expires once ```stat.tick()``` has advanced `duration` time.
+    /// The handle can still remove it earlier by being dropped
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut speed: Stat<2> = Stat::new(10.0);
+    /// let _haste = speed.add_modifier_with_duration(StatModifier::PercentAdd(0.5), 2.0);
+    /// speed.tick(1.0);
+    /// assert_eq!(speed.value(), 15.0);
+    /// speed.tick(1.0);
+    /// assert_eq!(speed.value(), 10.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn add_modifier_with_duration(
+        &mut self,
+        modifier: StatModifier,
+        duration: f32,
+    ) -> StatModifierHandle<S> {
+        self.add_modifier_meta(ModifierMeta {
+            order: modifier.default_order(),
+            modifier,
+            extras: ModifierExtras {
+                remaining_duration: Some(duration),
+                ..Default::default()
+            }
+            .boxed(),
+            ..Default::default()
+        })
+    }
+
+    /// Add a modifier using the default order, that only starts applying once ```stat.tick()``` has advanced `delay` time,
+    /// like "after 2 seconds in combat gain +10% damage". With a `duration` it expires that long after it activated
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut damage: Stat<2> = Stat::new(10.0);
+    /// let _in_combat = damage.add_modifier_with_delay(StatModifier::PercentAdd(0.1), 2.0, None);
+    /// damage.tick(1.0);
+    /// assert_eq!(damage.value(), 10.0);
+    /// damage.tick(1.0);
+    /// assert_eq!(damage.value(), 11.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn add_modifier_with_delay(
+        &mut self,
+        modifier: StatModifier,
+        delay: f32,
+        duration: Option<f32>,
+    ) -> StatModifierHandle<S> {
+        self.add_modifier_meta(ModifierMeta {
+            order: modifier.default_order(),
+            modifier,
+            extras: ModifierExtras {
+                remaining_delay: (delay > 0.0).then_some(delay),
+                remaining_duration: duration,
+                ..Default::default()
+            }
+            .boxed(),
+            ..Default::default()
+        })
+    }
+
+    /// Add a modifier using the default order, with a name to show in tooltips or while debugging.
+    /// The name can be read back through ```stat.modifiers()```
+    /// panics if refcell is borrowed
+    pub fn add_modifier_with_name(
+        &mut self,
+        modifier: StatModifier,
+        name: &'static str,
+    ) -> StatModifierHandle<S> {
+        self.add_modifier_meta(ModifierMeta {
+            order: modifier.default_order(),
+            modifier,
+            extras: ModifierExtras {
+                name: Some(name),
+                ..Default::default()
+            }
+            .boxed(),
+            ..Default::default()
+        })
+    }
+
+    /// Adds a modifier using the default order that doesn't change the value until it's committed through ```stat.commit_staged()```.
+    /// It takes up a slot in the meantime, ```stat.preview_staged()``` shows the value it would give.
+    /// Lets the UI preview a buff across frames before the player confirms it
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut attack: Stat<2> = Stat::new(10.0);
+    /// let staged = attack.stage_modifier(StatModifier::Flat(5.0));
+    /// assert_eq!((attack.value(), attack.preview_staged()), (10.0, 15.0));
+    /// let _handle = attack.commit_staged(staged).unwrap();
+    /// assert_eq!(attack.value(), 15.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn stage_modifier(&mut self, modifier: StatModifier) -> StagedHandle<S> {
+        let handle = S::new_handle();
+        self.push_modifier_meta(
+            &handle,
+            ModifierMeta {
+                order: modifier.default_order(),
+                modifier,
+                staged: true,
+                ..Default::default()
+            },
+        );
+        StagedHandle(handle)
+    }
+
+    /// Returns the value as if every staged modifier was committed, without changing the stat
+    /// panics if refcell is borrowed
+    pub fn preview_staged(&self) -> f32 {
+        self.update_modifiers();
+        let mut modifiers: Vec<ModifierMeta<S>> = S::borrow_cell(&self.modifiers).to_vec();
+        for modifier_meta in modifiers.iter_mut() {
+            modifier_meta.staged = false;
+        }
+        self.order_modifiers(&mut modifiers);
+
+        let mut value = self.base_value;
+        self.apply_modifiers_to_value(&modifiers, &mut value);
+        self.limit_value(self.base_value, &mut value);
+        self.effective_override().unwrap_or(value)
+    }
+
+    /// Applies a modifier added through ```stat.stage_modifier()```, returning the handle that controls it from now on.
+    /// Returns None if the staged modifier isn't in this stat
+    /// panics if refcell is borrowed
+    pub fn commit_staged(&mut self, staged: StagedHandle<S>) -> Option<StatModifierHandle<S>> {
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        let modifier_meta = modifiers
+            .iter_mut()
+            .find(|modifier_meta| modifier_meta.is_owned_by(&staged.0) && modifier_meta.staged)?;
+        modifier_meta.staged = false;
+        #[cfg(feature = "manual-pruning")]
+        {
+            modifier_meta.owner_modifier_strong = Some(staged.0.clone());
+        }
+        drop(modifiers);
+        self.calculate_internal_value();
+        Some(staged.0)
+    }
+
+    /// Discards a modifier added through ```stat.stage_modifier()```, the value never changed because of it.
+    /// Returns false if the staged modifier isn't in this stat
+    /// panics if refcell is borrowed
+    pub fn rollback_staged(&mut self, staged: StagedHandle<S>) -> bool {
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        let len = modifiers.len();
+        modifiers.retain(|modifier_meta| {
+            !(modifier_meta.is_owned_by(&staged.0) && modifier_meta.staged)
+        });
+        len != modifiers.len()
+    }
+
+    /// Iterates a copy of every valid modifier, in the order they are applied
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut attack: Stat<2> = Stat::new(10.0);
+    /// let _sword = attack.add_modifier_with_name(StatModifier::Flat(5.0), "Sword of Fire");
+    /// let names: Vec<_> = attack.modifiers().filter_map(|info| info.name).collect();
+    /// assert_eq!(names, ["Sword of Fire"]);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn modifiers(&self) -> impl Iterator<Item = ModifierInfo> {
+        self.update_modifiers();
+        let mut modifiers: Vec<ModifierMeta<S>> = S::borrow_cell(&self.modifiers)
+            .iter()
+            .filter(|modifier_meta| S::strong_count(&modifier_meta.owner_modifier_weak) > 0)
+            .cloned()
+            .collect();
+        // sorted here as well, the storage is only sorted when the value is calculated
+        self.order_modifiers(&mut modifiers);
+        modifiers.into_iter().map(|modifier_meta| ModifierInfo {
+            modifier: modifier_meta.modifier,
+            order: modifier_meta.order,
+            name: modifier_meta.extras().name,
+        })
+    }
+
+    /// Iterates the number every valid modifier holds, no matter the kind. See [`super::StatModifier::value()`]
+    /// panics if refcell is borrowed
+    pub fn modifier_values(&self) -> impl Iterator<Item = f32> {
+        self.modifiers().map(|info| info.modifier.value())
+    }
+
+    /// Add a modifier using the default order, that only applies while the condition value of the stat meets `condition`.
+    /// The condition value is set through ```stat.set_condition_value()```, conditional modifiers don't apply without one
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// # use game_stat::Condition;
+    /// let mut damage: Stat<2> = Stat::new(10.0);
+    /// // while below 50% health
+    /// let _berserk = damage.add_conditional_modifier(StatModifier::PercentAdd(0.5), Condition::Below(0.5));
+    /// damage.set_condition_value(Some(1.0));
+    /// assert_eq!(damage.value(), 10.0);
+    /// damage.set_condition_value(Some(0.2));
+    /// assert_eq!(damage.value(), 15.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn add_conditional_modifier(
+        &mut self,
+        modifier: StatModifier,
+        condition: Condition,
+    ) -> StatModifierHandle<S> {
+        self.add_modifier_meta(ModifierMeta {
+            order: modifier.default_order(),
+            modifier,
+            extras: ModifierExtras {
+                condition: Some(condition),
+                ..Default::default()
+            }
+            .boxed(),
+            ..Default::default()
+        })
+    }
+
+    /// Sets the value conditions of modifiers added with ```stat.add_conditional_modifier()``` are checked against
+    /// panics if refcell is borrowed
+    pub fn set_condition_value(&mut self, condition_value: Option<f32>) {
+        self.condition_value = condition_value;
+        self.calculate_internal_value();
+    }
+
+    /// Returns the value set through [`Stat::set_condition_value()`]
+    pub fn condition_value(&self) -> Option<f32> {
+        self.condition_value
+    }
+
+    /// Returns the value as if the condition value was `condition_value`, without changing the stat.
+    /// Lets a tooltip preview "below 50% health your damage would be X"
+    /// panics if refcell is borrowed
+    pub fn value_at_condition(&self, condition_value: f32) -> f32 {
+        self.update_modifiers();
+        let mut value = self.base_value;
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        self.order_modifiers(&mut modifiers);
+        self.apply_modifier_range(
+            &modifiers,
+            0..modifiers.len(),
+            Some(condition_value),
+            &mut value,
+            None,
+        );
+        self.limit_value(self.base_value, &mut value);
+        self.effective_override().unwrap_or(value)
+    }
+
+    /// Add a modifier using the default order, tagged so it can be counted with ```stat.count_by_tag()```.
+    /// What a tag means is up to you, like an id for every stack of the same buff
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// const POISON: u64 = 1;
+    /// let mut health: Stat<4> = Stat::new(100.0);
+    /// let _stack_1 = health.add_modifier_with_tag(StatModifier::Flat(-5.0), POISON);
+    /// let _stack_2 = health.add_modifier_with_tag(StatModifier::Flat(-5.0), POISON);
+    /// assert_eq!(health.count_by_tag(POISON), 2);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn add_modifier_with_tag(
+        &mut self,
+        modifier: StatModifier,
+        tag: u64,
+    ) -> StatModifierHandle<S> {
+        self.add_modifier_meta(ModifierMeta {
+            order: modifier.default_order(),
+            modifier,
+            extras: ModifierExtras {
+                tag: Some(tag),
+                ..Default::default()
+            }
+            .boxed(),
+            ..Default::default()
+        })
+    }
+
+    /// Add a modifier using the default order, coming from `source`. See ```stat.remove_by_source()```
+    /// panics if refcell is borrowed
+    pub fn add_modifier_with_source(
+        &mut self,
+        modifier: StatModifier,
+        source: ModifierSource,
+    ) -> StatModifierHandle<S> {
+        self.add_modifier_meta(ModifierMeta {
+            order: modifier.default_order(),
+            modifier,
+            extras: ModifierExtras {
+                source,
+                ..Default::default()
+            }
+            .boxed(),
+            ..Default::default()
+        })
+    }
+
+    /// Removes every modifier coming from `source`, even if their handle still exists. Returns how many were removed.
+    /// Modifiers added without a source have ```ModifierSource::Unspecified```
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// # use game_stat::ModifierSource;
+    /// let mut health: Stat<2> = Stat::new(100.0);
+    /// let _armor = health.add_modifier_with_source(StatModifier::Flat(20.0), ModifierSource::Equipment);
+    /// let _potion = health.add_modifier_with_source(StatModifier::Flat(50.0), ModifierSource::Consumable);
+    /// assert_eq!(health.remove_by_source(ModifierSource::Consumable), 1);
+    /// assert_eq!(health.value(), 120.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn remove_by_source(&mut self, source: ModifierSource) -> usize {
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        let len = modifiers.len();
+        modifiers.retain(|modifier_meta| {
+            modifier_meta.extras().source != source
+                || S::strong_count(&modifier_meta.owner_modifier_weak) == 0
+        });
+        let removed = len - modifiers.len();
+        drop(modifiers);
+        if removed > 0 {
+            self.calculate_internal_value();
+        }
+        removed
+    }
+
+    /// Add a modifier using the default order to an exclusive group: only the strongest modifier of the group applies,
+    /// the others are kept but suppressed. Once the strongest is dropped the next one takes over.
+    /// Strength is how far a modifier moves the value in either direction, so the harshest debuff wins as well
+    /// (```Flat(-5.0)``` over ```Flat(-2.0)```, ```PercentMultiply(0.5)``` over ```PercentMultiply(0.8)```).
+    /// Meant for modifiers of the same kind, like movement speed auras
+    /// ```
+    /// # #[cfg(not(feature = "manual-pruning"))]
+    /// # {
+    /// # use game_stat::prelude::*;
+    /// const SPEED_AURA: u16 = 0;
+    /// let mut speed: Stat<2> = Stat::new(10.0);
+    /// let _weak_aura = speed.add_modifier_with_group(StatModifier::PercentAdd(0.2), SPEED_AURA);
+    /// let strong_aura = speed.add_modifier_with_group(StatModifier::PercentAdd(0.5), SPEED_AURA);
+    /// assert_eq!(speed.value(), 15.0);
+    /// drop(strong_aura);
+    /// assert_eq!(speed.value(), 12.0);
+    /// # }
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn add_modifier_with_group(
+        &mut self,
+        modifier: StatModifier,
+        group: u16,
+    ) -> StatModifierHandle<S> {
+        self.add_modifier_meta(ModifierMeta {
+            order: modifier.default_order(),
+            modifier,
+            extras: ModifierExtras {
+                group: Some(group),
+                ..Default::default()
+            }
+            .boxed(),
+            ..Default::default()
+        })
+    }
+
+    /// Returns how many valid modifiers were added with `tag`
+    /// panics if refcell is borrowed
+    pub fn count_by_tag(&self, tag: u64) -> usize {
+        let modifiers = S::borrow_cell(&self.modifiers);
+        modifiers
+            .iter()
+            .filter(|modifier_meta| {
+                modifier_meta.extras().tag == Some(tag)
+                    && S::strong_count(&modifier_meta.owner_modifier_weak) > 0
+            })
+            .count()
+    }
+
+    /// Advances the time of every modifier added with ```stat.add_modifier_with_duration()```,
+    /// removing the ones that expired. Returns the expired modifiers, so the UI can show them going away
+    /// panics if refcell is borrowed
+    pub fn tick(&mut self, delta_time: f32) -> Vec<ModifierDef> {
+        self.update_modifiers();
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        let mut expired = Vec::new();
+        let mut activated = false;
+        for modifier_meta in modifiers.iter_mut() {
+            let Some(extras) = modifier_meta.extras.as_deref_mut() else {
+                continue;
+            };
+            // time left over after the delay counts towards the duration
+            let mut delta_time = delta_time;
+            if let Some(remaining_delay) = extras.remaining_delay.as_mut() {
+                *remaining_delay -= delta_time;
+                if *remaining_delay > 0.0 {
+                    continue;
+                }
+                delta_time = -*remaining_delay;
+                extras.remaining_delay = None;
+                activated = true;
+            }
+            if let Some(remaining_duration) = extras.remaining_duration.as_mut() {
+                *remaining_duration -= delta_time;
+                if *remaining_duration <= 0.0 {
+                    expired.push(modifier_meta.def());
+                }
+            }
+        }
+        if !expired.is_empty() {
+            // exactly the expired ones, a modifier still waiting for its delay hasn't started its duration
+            modifiers.retain(|modifier_meta| {
+                let extras = modifier_meta.extras();
+                extras.remaining_delay.is_some()
+                    || extras
+                        .remaining_duration
+                        .is_none_or(|remaining_duration| remaining_duration > 0.0)
+            });
+        }
+        drop(modifiers);
+
+        if activated || !expired.is_empty() {
+            self.calculate_internal_value();
+        }
+        expired
+    }
+
+    /// Uses up a charge of the modifier belonging to `handle`, the modifier is removed when no charges are left.
+    /// Returns the remaining charges, None if the handle has no modifier with charges in this stat
+    /// panics if refcell is borrowed
+    pub fn consume_charge(&mut self, handle: &StatModifierHandle<S>) -> Option<u32> {
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        let index = modifiers
+            .iter()
+            .position(|modifier_meta| modifier_meta.is_owned_by(handle))?;
+        let charges = modifiers[index].extras.as_deref_mut()?.charges.as_mut()?;
+        *charges = charges.saturating_sub(1);
+        let remaining = *charges;
+        if remaining == 0 {
+            modifiers.remove(index);
+            drop(modifiers);
+            self.calculate_internal_value();
+        }
+        Some(remaining)
+    }
+
+    /// Swaps the modifier belonging to `handle` for `new_modifier`, keeping its order and charges.
+    /// The handle stays valid, so a growing aura can update its magnitude without being added again.
+    /// Returns false if the handle has no modifier in this stat
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut attack: Stat<2> = Stat::new(10.0);
+    /// let aura = attack.add_modifier(StatModifier::Flat(1.0));
+    /// attack.replace_modifier(&aura, StatModifier::Flat(3.0));
+    /// assert_eq!(attack.value(), 13.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn replace_modifier(
+        &mut self,
+        handle: &StatModifierHandle<S>,
+        new_modifier: StatModifier,
+    ) -> bool {
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        let Some(modifier_meta) = modifiers
+            .iter_mut()
+            .find(|modifier_meta| modifier_meta.is_owned_by(handle))
+        else {
+            return false;
+        };
+        modifier_meta.modifier = new_modifier;
+        drop(modifiers);
+        self.calculate_internal_value();
+        true
+    }
+
+    /// Moves the modifier belonging to `handle` to another order, the handle stays valid.
+    /// Returns false if the handle has no modifier in this stat
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut attack: Stat<2> = Stat::new(10.0);
+    /// let flat = attack.add_modifier_with_order(StatModifier::Flat(5.0), 0);
+    /// let _double = attack.add_modifier_with_order(StatModifier::PercentMultiply(2.0), 10);
+    /// attack.set_modifier_order(&flat, 20);
+    /// assert_eq!(attack.value(), 25.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn set_modifier_order(&mut self, handle: &StatModifierHandle<S>, order: i32) -> bool {
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        let Some(modifier_meta) = modifiers
+            .iter_mut()
+            .find(|modifier_meta| modifier_meta.is_owned_by(handle))
+        else {
+            return false;
+        };
+        modifier_meta.order = order;
+        drop(modifiers);
+        self.calculate_internal_value();
+        true
+    }
+
+    /// Removes the modifier belonging to `handle`, returns false if the handle has no modifier in this stat.
+    ///
+    /// With **manual-pruning** this is the only way a modifier gets removed, dropping the handle does nothing
+    /// panics if refcell is borrowed
+    #[cfg(feature = "manual-pruning")]
+    pub fn remove_modifier(&mut self, handle: &StatModifierHandle<S>) -> bool {
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        let len = modifiers.len();
+        modifiers.retain(|modifier_meta| !modifier_meta.is_owned_by(handle));
+        let removed = modifiers.len() != len;
+        drop(modifiers);
+
+        if removed {
+            self.calculate_internal_value();
+        }
+        removed
+    }
+
+    // creates a handle for the modifier and adds it
+    /// panics if refcell is borrowed
+    fn add_modifier_meta(&mut self, modifier_meta: ModifierMeta<S>) -> StatModifierHandle<S> {
+        let handle = S::new_handle();
+        self.attach_modifier_meta(&handle, modifier_meta);
+        handle
+    }
+
+    // adds the modifier, controlled by an existing handle
+    /// panics if refcell is borrowed
+    fn attach_modifier_meta(
+        &mut self,
+        handle: &StatModifierHandle<S>,
+        modifier_meta: ModifierMeta<S>,
+    ) {
+        self.push_modifier_meta(handle, modifier_meta);
+        // value needs to update
+        self.calculate_internal_value();
+    }
+
+    /// Adds every valid modifier of `self` onto `target`, keeping the base value and configuration of `target`.
+    /// Everything attached to the modifiers (order, charges, durations, tags...) is copied,
+    /// returns the new handles in the order the modifiers are applied in `self`.
+    /// All or nothing: if `target` can't fit every modifier on the stack (`N`), nothing is added.
+    /// A single ```stat.add_modifier()``` may spill to the heap, a whole loadout spilling is most likely a wrong `N`
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut template: Stat<2> = Stat::new(0.0);
+    /// let _handle = template.add_modifier(StatModifier::Flat(5.0));
+    /// let mut attack: Stat<4> = Stat::new(10.0);
+    /// let _handles = template.clone_config_onto(&mut attack).unwrap();
+    /// assert_eq!(attack.value(), 15.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn clone_config_onto<const N: usize>(
+        &self,
+        target: &mut Stat<N, S>,
+    ) -> Result<Vec<StatModifierHandle<S>>, ModifiersFullError> {
+        self.update_modifiers();
+        target.update_modifiers();
+        let needed = self.len();
+        if !target.can_fit(needed) {
+            return Err(ModifiersFullError {
+                needed,
+                available: N.saturating_sub(target.len()),
+            });
+        }
+        let modifiers = S::borrow_cell(&self.modifiers);
+        let handles = modifiers
+            .iter()
+            .filter(|modifier_meta| S::strong_count(&modifier_meta.owner_modifier_weak) > 0)
+            .map(|modifier_meta| {
+                let handle = S::new_handle();
+                let mut modifier_meta = modifier_meta.clone();
+                // a permanent modifier stays permanent, owned by its new handle
+                modifier_meta.owner_modifier_strong = modifier_meta
+                    .owner_modifier_strong
+                    .as_ref()
+                    .map(|_| handle.clone());
+                target.push_modifier_meta(&handle, modifier_meta);
+                handle
+            })
+            .collect();
+        drop(modifiers);
+        target.calculate_internal_value();
+        Ok(handles)
+    }
+
+    /// Consumes the stat and adds its valid modifiers onto `target`, like [`Stat::clone_config_onto()`].
+    /// All or nothing the same way: if `target` can't fit every modifier on the stack (`N`), nothing is added and the stat is handed back
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut equipment: Stat<2> = Stat::new(0.0);
+    /// let _sword = equipment.add_modifier(StatModifier::Flat(5.0));
+    /// let mut attack: Stat<4> = Stat::new(10.0);
+    /// let _handles = equipment.merge_into(&mut attack).unwrap();
+    /// assert_eq!(attack.value(), 15.0);
+    /// ```
+    /// panics if refcell is borrowed
+    // handing the stat back is the whole point of the error
+    #[allow(clippy::result_large_err)]
+    pub fn merge_into<const N: usize>(
+        self,
+        target: &mut Stat<N, S>,
+    ) -> Result<Vec<StatModifierHandle<S>>, Self> {
+        match self.clone_config_onto(target) {
+            Ok(handles) => Ok(handles),
+            Err(_) => Err(self),
+        }
+    }
+
+    // Same as add_modifier_with_order(), but the value is only calculated the next time it's read.
+    // Lets a sheet skip stats that are never read
+    /// panics if refcell is borrowed
+    pub(crate) fn add_modifier_with_order_lazy(
+        &mut self,
+        modifier: StatModifier,
+        order: i32,
+    ) -> StatModifierHandle<S> {
+        let handle = S::new_handle();
+        self.push_modifier_meta(
+            &handle,
+            ModifierMeta {
+                modifier,
+                order,
+                ..Default::default()
+            },
+        );
+        self.mark_dirty();
+        handle
+    }
+
+    // adds the modifier without recalculating the value
+    /// panics if refcell is borrowed
+    fn push_modifier_meta(
+        &mut self,
+        handle: &StatModifierHandle<S>,
+        mut modifier_meta: ModifierMeta<S>,
+    ) {
+        // We have to update the modifiers array in case one has been dropped.
+        // The modifier array could be full of data, yet have modifiers that aren't valid.
+        // If we drop a modifier and then add one right away, there should be space for it to be added.
+        // This ensures the array is up to date.
+        self.remove_dropped_modifiers();
+        modifier_meta.owner_modifier_weak = S::downgrade(handle);
+        modifier_meta.sequence = self.next_sequence;
+        self.next_sequence += 1;
+        // a staged modifier only keeps its handle alive once committed, dropping the StagedHandle discards it
+        #[cfg(feature = "manual-pruning")]
+        if !modifier_meta.staged {
+            modifier_meta.owner_modifier_strong = Some(handle.clone());
+        }
+
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        if modifiers.len() + 1 > modifiers.capacity() {
+            modifiers.move_to_the_heap();
+        }
+        modifiers.push(modifier_meta);
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            stat = ?(self as *const Self),
+            modifier = ?modifiers.last().map(|modifier_meta| modifier_meta.modifier),
+            modifier_count = modifiers.len(),
+            "added modifier"
+        );
+    }
+
+    /// Gives mutable access to the order of every valid modifier, the modifiers themselves can only be read.
+    /// The stat recalculates on the next read after the returned [`ModifiersMut`] is dropped
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut stat: Stat<2> = Stat::new(10.0);
+    /// let _flat = stat.add_modifier(StatModifier::Flat(10.0));
+    /// let _multiply = stat.add_modifier(StatModifier::PercentMultiply(2.0));
+    /// assert_eq!(stat.value(), 40.0);
+    /// for mut modifier in stat.modifiers_mut().iter_mut() {
+    ///     if let StatModifier::Flat(_) = modifier.modifier() {
+    ///         modifier.set_order(10);
+    ///     }
+    /// }
+    /// assert_eq!(stat.value(), 30.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn modifiers_mut(&mut self) -> ModifiersMut<'_, M, S> {
+        self.update_modifiers();
+        ModifiersMut {
+            modifiers: S::borrow_cell(&self.modifiers),
+            dirty: &self.dirty,
+        }
+    }
+
+    /// Gives mutable access to the base value, the stat recalculates on the next read after the returned guard is dropped
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut strength: Stat<2> = Stat::new(10.0);
+    /// let _handle = strength.add_modifier(StatModifier::PercentMultiply(2.0));
+    /// *strength.base_value_mut() += 5.0;
+    /// assert_eq!(strength.value(), 30.0);
+    /// ```
+    pub fn base_value_mut(&mut self) -> BaseValueGuard<'_, M, S> {
+        BaseValueGuard { stat: self }
+    }
+
+    /// Makes the current value permanent: the base value becomes ```stat.value()``` and every modifier is removed,
+    /// their handles no longer control anything. For leveling systems where temporary growth becomes permanent.
+    /// An active override isn't baked, the base value becomes what the value would be without it
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut strength: Stat<2> = Stat::new(10.0);
+    /// let _training = strength.add_modifier(StatModifier::Flat(5.0));
+    /// strength.bake();
+    /// assert_eq!((strength.base_value, strength.value(), strength.len()), (15.0, 15.0, 0));
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn bake(&mut self) {
+        self.base_value = self.value_without_override(true);
+        self.clear_modifiers();
+    }
+
+    /// Same as [`Stat::bake()`], using the value before the bounds are applied (see ```stat.raw_value()```),
+    /// so growth above the max bound isn't lost if the bounds change later
+    /// panics if refcell is borrowed
+    pub fn bake_raw(&mut self) {
+        self.base_value = self.value_without_override(false);
+        self.clear_modifiers();
+    }
+
+    // the calculated value ignoring any override, optionally without the bounds
+    fn value_without_override(&self, clamp: bool) -> f32 {
+        self.update_modifiers();
+        let mut value = self.base_value;
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        self.order_modifiers(&mut modifiers);
+        self.apply_modifiers_to_value(&modifiers, &mut value);
+        drop(modifiers);
+        self.limit_multipliers_and_round(self.base_value, &mut value);
+        if clamp {
+            self.clamp(&mut value);
+        }
+        value
+    }
+
+    // removes every modifier, including permanent ones
+    fn clear_modifiers(&mut self) {
+        S::borrow_cell(&self.modifiers).clear();
+        self.calculate_internal_value();
+    }
+
+    /// Scales the effect of every percent modifier by `factor`, flat modifiers are left alone.
+    /// ```StatModifier::PercentAdd(0.4)``` scaled by 0.5 becomes ```PercentAdd(0.2)```,
+    /// ```StatModifier::PercentMultiply(3.0)``` scaled by 0.5 becomes ```PercentMultiply(2.0)``` (the distance from 1.0 is scaled)
+    ///
+    /// The scaling is relative to the current modifiers, calling it twice with 0.5 is the same as calling it once with 0.25
+    /// panics if refcell is borrowed
+    pub fn scale_percent_modifiers(&mut self, factor: f32) {
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        for modifier_meta in modifiers.iter_mut() {
+            match &mut modifier_meta.modifier {
+                StatModifier::PercentAdd(v)
+                | StatModifier::Increased(v)
+                | StatModifier::More(v) => *v *= factor,
+                StatModifier::PercentMultiply(v) => *v = 1.0 + (*v - 1.0) * factor,
+                _ => {}
+            }
+        }
+        drop(modifiers);
+
+        self.calculate_internal_value();
+    }
+
+    /// Removes every modifier whose handle was dropped and recalculates the value if any were removed.
+    /// Returns how many modifiers were removed.
+    ///
+    /// Cleanup already happens lazily on reads and adds, this lets you reclaim the space at a moment of your choosing (like once per frame)
+    /// panics if refcell is borrowed
+    pub fn prune(&mut self) -> usize {
+        self.update_modifiers()
+    }
+
+    /// Recalculates the value if it's outdated (a handle was dropped, or a change was deferred to the next read),
+    /// returning whether it did. Lets you schedule the work at a fixed point, like the end of a frame
+    /// ```
+    /// # #[cfg(not(feature = "manual-pruning"))]
+    /// # {
+    /// # use game_stat::prelude::*;
+    /// let mut armor: Stat<2> = Stat::new(10.0);
+    /// let handle = armor.add_modifier(StatModifier::Flat(5.0));
+    /// assert!(!armor.recalculate_if_dirty());
+    /// drop(handle);
+    /// assert!(armor.recalculate_if_dirty());
+    /// assert_eq!(armor.cached_value(), 10.0);
+    /// # }
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn recalculate_if_dirty(&mut self) -> bool {
+        self.refresh().1
+    }
+
+    /// returns how many modifiers were removed
+    fn update_modifiers(&self) -> usize {
+        self.refresh().0
+    }
+
+    // removes dropped modifiers and overrides, recalculating if needed.
+    // Returns how many modifiers were removed and whether the value was recalculated
+    fn refresh(&self) -> (usize, bool) {
+        let dropped_modifiers = self.remove_dropped_modifiers();
+        let dropped_overrides = {
+            let mut override_stack = S::borrow_cell(&self.override_stack);
+            let len = override_stack.len();
+            override_stack.retain(|(_, owner_weak)| S::strong_count(owner_weak) > 0);
+            len - override_stack.len()
+        };
+        let outdated =
+            dropped_modifiers > 0 || dropped_overrides > 0 || *S::borrow_cell(&self.dirty);
+        if outdated {
+            self.calculate_internal_value();
+        }
+        (dropped_modifiers, outdated)
+    }
+
+    // same as update_modifiers() without recalculating, returns how many modifiers were removed
+    fn remove_dropped_modifiers(&self) -> usize {
+        // with manual-pruning modifiers keep their handle alive, only uncommitted staged modifiers can be dropped
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        let len = modifiers.len();
+        modifiers.retain(|m| S::strong_count(&m.owner_modifier_weak) > 0);
+        let dropped_modifiers = len - modifiers.len();
+        #[cfg(feature = "tracing")]
+        if dropped_modifiers > 0 {
+            tracing::trace!(
+                stat = ?(self as *const Self),
+                dropped_modifiers,
+                modifier_count = modifiers.len(),
+                "pruned modifiers"
+            );
+        }
+        dropped_modifiers
+    }
+
+    // the value is calculated again on the next read
+    pub(crate) fn mark_dirty(&self) {
+        *S::borrow_cell(&self.dirty) = true;
     }
 
     /// returns base value with modifiers applied from self AND other stats's modifiers
@@ -228,24 +2248,239 @@ impl<const M: usize> Stat<M> {
         other_stat.update_modifiers();
         let highest_order = self.highest_order();
 
-        let mut other_modifiers = borrow_cell(&other_stat.modifiers);
-        let mut temporary_handles: TinyVec<[StatModifierHandle; M]> =
-            TinyVec::with_capacity(other_modifiers.len());
+        // computed on the side, so self never recalculates with the borrowed modifiers
+        let mut modifiers: Vec<ModifierMeta<S>> = S::borrow_cell(&self.modifiers).to_vec();
+        let other_modifiers = S::borrow_cell(&other_stat.modifiers);
+        for (index, modifier_meta) in other_modifiers.iter().enumerate() {
+            modifiers.push(ModifierMeta {
+                modifier: modifier_meta.modifier,
+                order: highest_order + 1 + modifier_meta.order,
+                sequence: self.next_sequence + index as u64,
+                owner_modifier_weak: modifier_meta.owner_modifier_weak.clone(),
+                ..Default::default()
+            });
+        }
+        drop(other_modifiers);
+        self.order_modifiers(&mut modifiers);
+
+        let mut value = self.base_value;
+        self.apply_modifiers_to_value(&modifiers, &mut value);
+        self.limit_value(self.base_value, &mut value);
+        self.effective_override().unwrap_or(value)
+    }
+
+    /// Returns the value with the valid modifiers of every aura applied along with the modifiers of self,
+    /// like a party aura granting the bonuses of nearby allies. Everything is sorted together by order,
+    /// the base values of the auras aren't taken into account and self is left untouched
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut attack: Stat<2> = Stat::new(10.0);
+    /// let _sword = attack.add_modifier(StatModifier::Flat(5.0));
+    /// let mut banner: Stat<2> = Stat::new(0.0);
+    /// let _rally = banner.add_modifier(StatModifier::PercentAdd(0.2));
+    /// assert_eq!(attack.value_with_auras(&[&banner]), 18.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn value_with_auras<const N: usize>(&self, auras: &[&Stat<N, S>]) -> f32 {
+        self.update_modifiers();
+        let mut modifiers: Vec<ModifierMeta<S>> = S::borrow_cell(&self.modifiers).to_vec();
+        for aura in auras {
+            aura.update_modifiers();
+            modifiers.extend(S::borrow_cell(&aura.modifiers).iter().cloned());
+        }
+        self.order_modifiers(&mut modifiers);
+
+        let mut value = self.base_value;
+        self.apply_modifiers_to_value(&modifiers, &mut value);
+        self.limit_value(self.base_value, &mut value);
+        self.effective_override().unwrap_or(value)
+    }
+
+    /// Returns how many modifiers are valid (their handle still exists)
+    /// panics if refcell is borrowed
+    pub fn len(&self) -> usize {
+        let modifiers = S::borrow_cell(&self.modifiers);
+        modifiers
+            .iter()
+            .filter(|modifier_meta| S::strong_count(&modifier_meta.owner_modifier_weak) > 0)
+            .count()
+    }
+
+    /// Returns true if no modifier is valid, for "no active effects" states.
+    /// Modifiers whose handle was dropped don't count, even before they are cleaned up
+    /// ```
+    /// # #[cfg(not(feature = "manual-pruning"))]
+    /// # {
+    /// # use game_stat::prelude::*;
+    /// let mut armor: Stat<2> = Stat::new(10.0);
+    /// let handle = armor.add_modifier(StatModifier::Flat(5.0));
+    /// assert!(!armor.is_empty());
+    /// drop(handle);
+    /// assert!(armor.is_empty());
+    /// # }
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns how many modifiers are stored, including the ones whose handle got dropped,
+    /// but haven't been cleaned up yet. Cleanup happens on the next read or add, like ```stat.value()```
+    /// panics if refcell is borrowed
+    pub fn raw_slot_count(&self) -> usize {
+        S::borrow_cell(&self.modifiers).len()
+    }
+
+    /// Returns how full the stack storage is, ```stat.len() / M```, to spot stats that need a bigger `M`.
+    /// Goes above 1.0 once the modifiers spilled to the heap. A ```Stat<0>``` always returns 1.0
+    /// panics if refcell is borrowed
+    pub fn capacity_utilization(&self) -> f32 {
+        if M == 0 {
+            return 1.0;
+        }
+        self.len() as f32 / M as f32
+    }
+
+    /// Returns true if `n` more modifiers fit on the stack without spilling to the heap.
+    /// Modifiers whose handle was dropped don't count, even if they weren't cleaned up yet,
+    /// since adding a modifier cleans them up first
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut stat: Stat<2> = Stat::new(10.0);
+    /// let _sword = stat.add_modifier(StatModifier::Flat(5.0));
+    /// assert!(stat.can_fit(1));
+    /// assert!(!stat.can_fit(2));
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn can_fit(&self, n: usize) -> bool {
+        n <= M.saturating_sub(self.len())
+    }
+
+    /// Iterates the (kind, order) of every stored modifier whose handle was dropped, but wasn't cleaned up yet.
+    /// Doesn't clean anything up itself, so it can be used to look into churn before ```stat.prune()```
+    /// panics if refcell is borrowed
+    pub fn dead_modifiers(&self) -> impl Iterator<Item = (ModifierKind, i32)> {
+        let modifiers = S::borrow_cell(&self.modifiers);
+        modifiers
+            .iter()
+            .filter(|modifier_meta| S::strong_count(&modifier_meta.owner_modifier_weak) == 0)
+            .map(|modifier_meta| (modifier_meta.modifier.kind(), modifier_meta.order))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Returns a hash of everything that decides the value: base value, configuration, overrides and the valid modifiers
+    /// with their order, delay and condition.
+    /// The hash only changes when the configuration does, so it can be used to detect when something like a tooltip needs to update
+    /// panics if refcell is borrowed
+    pub fn config_hash(&self) -> u64 {
+        let mut hasher = std::hash::DefaultHasher::new();
+        self.base_value.to_bits().hash(&mut hasher);
+        self.min_value.map(f32::to_bits).hash(&mut hasher);
+        self.max_value.map(f32::to_bits).hash(&mut hasher);
+        self.clamp_between_phases.hash(&mut hasher);
+        self.min_multiplier.map(f32::to_bits).hash(&mut hasher);
+        self.max_multiplier.map(f32::to_bits).hash(&mut hasher);
+        self.rounding.hash(&mut hasher);
+        self.step_rounding.hash(&mut hasher);
+        self.tie_break.hash(&mut hasher);
+        self.f64_accumulation.hash(&mut hasher);
+        self.condition_value.map(f32::to_bits).hash(&mut hasher);
+        self.override_value.map(f32::to_bits).hash(&mut hasher);
+        for (value, _) in S::borrow_cell(&self.override_stack)
+            .iter()
+            .filter(|(_, owner_weak)| S::strong_count(owner_weak) > 0)
+        {
+            value.to_bits().hash(&mut hasher);
+        }
+
+        let modifiers = S::borrow_cell(&self.modifiers);
+        for modifier_meta in modifiers
+            .iter()
+            .filter(|modifier_meta| S::strong_count(&modifier_meta.owner_modifier_weak) > 0)
+        {
+            std::mem::discriminant(&modifier_meta.modifier).hash(&mut hasher);
+            modifier_meta.modifier.value().to_bits().hash(&mut hasher);
+            modifier_meta.order.hash(&mut hasher);
+            let extras = modifier_meta.extras();
+            extras.group.hash(&mut hasher);
+            modifier_meta.staged.hash(&mut hasher);
+            // only whether it's still waiting, the time left changes every tick
+            extras.remaining_delay.is_some().hash(&mut hasher);
+            match extras.condition {
+                None => 0u8.hash(&mut hasher),
+                Some(Condition::Below(threshold)) => (1u8, threshold.to_bits()).hash(&mut hasher),
+                Some(Condition::AtLeast(threshold)) => (2u8, threshold.to_bits()).hash(&mut hasher),
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Returns the order the modifier belonging to `handle` is applied at, None if the handle has no modifier in this stat.
+    /// Useful after ```stat.add_modifier()``` to find out which default order it got
+    /// panics if refcell is borrowed
+    pub fn effective_order(&self, handle: &StatModifierHandle<S>) -> Option<i32> {
+        let modifiers = S::borrow_cell(&self.modifiers);
+        modifiers
+            .iter()
+            .find(|modifier_meta| modifier_meta.is_owned_by(handle))
+            .map(|modifier_meta| modifier_meta.order)
+    }
+
+    /// Returns a readable multi-line description of the stat, for snapshot tests or debugging.
+    /// Lists the base value, any bounds or override, every valid modifier in the order it is applied and the final value
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut stat: Stat<2> = Stat::new(10.0);
+    /// let _handle = stat.add_modifier(StatModifier::Flat(5.0));
+    /// assert_eq!(stat.describe(), "base: 10\nFlat(5.0) order: 0\nvalue: 15\n");
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn describe(&self) -> String {
+        use std::fmt::Write;
 
-        for modifier in other_modifiers.iter_mut() {
-            temporary_handles.push(self.add_modifier_with_order(
-                modifier.modifier.clone(),
-                highest_order + 1 + modifier.order,
-            ));
+        let value = self.value();
+        let mut description = format!("base: {}\n", self.base_value);
+        if self.min_value.is_some() || self.max_value.is_some() {
+            let _ = writeln!(
+                description,
+                "bounds: {:?}..{:?}",
+                self.min_value, self.max_value
+            );
+        }
+        if let Some(rounding) = self.rounding {
+            let _ = writeln!(description, "rounding: {:?}", rounding);
+        }
+        if let Some(step_rounding) = self.step_rounding {
+            let _ = writeln!(description, "step rounding: {:?}", step_rounding);
+        }
+        if let Some(override_value) = self.effective_override() {
+            let _ = writeln!(description, "override: {}", override_value);
+        }
+        let modifiers = S::borrow_cell(&self.modifiers);
+        for modifier_meta in modifiers
+            .iter()
+            .filter(|modifier_meta| S::strong_count(&modifier_meta.owner_modifier_weak) > 0)
+        {
+            let _ = write!(
+                description,
+                "{:?} order: {}",
+                modifier_meta.modifier, modifier_meta.order
+            );
+            if let Some(name) = modifier_meta.extras().name {
+                let _ = write!(description, " name: {}", name);
+            }
+            description.push('\n');
         }
-        self.value()
+        let _ = writeln!(description, "value: {}", value);
+        description
     }
 
     /// Returns the highest order of all modifiers
     /// panics if refcell is borrowed
     pub fn highest_order(&self) -> i32 {
         self.update_modifiers();
-        let modifiers = borrow_cell(&self.modifiers);
+        let modifiers = S::borrow_cell(&self.modifiers);
         modifiers
             .iter()
             .map(|modifier_meta| modifier_meta.order)
@@ -253,49 +2488,634 @@ impl<const M: usize> Stat<M> {
             .unwrap_or(0)
     }
 
+    /// Returns the (lowest, highest) order of the valid modifiers, None if there are none.
+    /// Orders outside of the span can be used for new phases without colliding with existing ones
+    /// panics if refcell is borrowed
+    pub fn order_span(&self) -> Option<(i32, i32)> {
+        let modifiers = S::borrow_cell(&self.modifiers);
+        modifiers
+            .iter()
+            .filter(|modifier_meta| S::strong_count(&modifier_meta.owner_modifier_weak) > 0)
+            .map(|modifier_meta| modifier_meta.order)
+            .fold(None, |span, order| match span {
+                None => Some((order, order)),
+                Some((low, high)) => Some((low.min(order), high.max(order))),
+            })
+    }
+
+    /// Iterates the distinct orders the valid modifiers use, lowest first. Helps picking a free order for a new phase
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut attack: Stat<4> = Stat::new(10.0);
+    /// let _sword = attack.add_modifier(StatModifier::Flat(5.0));
+    /// let _ring = attack.add_modifier(StatModifier::Flat(1.0));
+    /// let _rage = attack.add_modifier_with_order(StatModifier::PercentAdd(0.5), 10);
+    /// assert!(attack.modifier_orders().eq([0, 10]));
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn modifier_orders(&self) -> impl Iterator<Item = i32> {
+        let mut orders: Vec<i32> = self.modifiers().map(|info| info.order).collect();
+        orders.sort_unstable();
+        orders.dedup();
+        orders.into_iter()
+    }
+
+    /// Panics if the internal state of the stat is inconsistent, meant for catching bugs in tests.
+    /// Only exists in debug builds. After bringing the stat up to date it checks that:
+    /// * no modifier whose handle was dropped is still stored
+    /// * the modifiers are sorted by order
+    /// * the cached value is the same as calculating it from scratch
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut stat: Stat<2> = Stat::new(10.0);
+    /// let _handle = stat.add_modifier(StatModifier::Flat(5.0));
+    /// stat.debug_validate();
+    /// ```
+    /// panics if refcell is borrowed
+    #[cfg(debug_assertions)]
+    pub fn debug_validate(&self) {
+        self.update_modifiers();
+        let modifiers = S::borrow_cell(&self.modifiers);
+        assert!(
+            modifiers
+                .iter()
+                .all(|modifier_meta| S::strong_count(&modifier_meta.owner_modifier_weak) > 0),
+            "a modifier with a dropped handle wasn't removed"
+        );
+        assert!(
+            modifiers
+                .windows(2)
+                .all(|pair| pair[0].order <= pair[1].order),
+            "modifiers aren't sorted by order"
+        );
+        drop(modifiers);
+
+        let fresh_value = self.recompute_from_scratch();
+        let value = *S::borrow_cell(&self.value);
+        assert!(
+            value.to_bits() == fresh_value.to_bits() || (value.is_nan() && fresh_value.is_nan()),
+            "cached value {} doesn't match the calculated value {}",
+            value,
+            fresh_value
+        );
+    }
+
+    /// Calculates the value from a sorted copy of the valid modifiers, without reading or updating anything cached.
+    /// Used by tests to check ```stat.value()``` against, not meant for regular use
+    /// panics if refcell is borrowed
+    #[doc(hidden)]
+    pub fn recompute_from_scratch(&self) -> f32 {
+        let mut modifiers: Vec<ModifierMeta<S>> = S::borrow_cell(&self.modifiers)
+            .iter()
+            .filter(|modifier_meta| S::strong_count(&modifier_meta.owner_modifier_weak) > 0)
+            .cloned()
+            .collect();
+        self.order_modifiers(&mut modifiers);
+
+        let mut value = self.base_value;
+        self.apply_modifiers_to_value(&modifiers, &mut value);
+        self.limit_value(self.base_value, &mut value);
+        self.effective_override().unwrap_or(value)
+    }
+
     /// Returns the internal base_value with modifiers applied
     /// panics if refcell is borrowed
     pub fn value(&self) -> f32 {
         self.update_modifiers();
-        *borrow_cell(&self.value)
+        let value = *S::borrow_cell(&self.value);
+        #[cfg(feature = "history")]
+        {
+            let mut history = S::borrow_cell(&self.history);
+            if history.pending {
+                history.pending = false;
+                if history.len == 0 || history.values[history.len - 1] != value {
+                    history.push(value);
+                }
+            }
+        }
+        value
+    }
+
+    /// Returns the value calculated last, without pruning dropped modifiers or recalculating.
+    /// The value can be stale: after a handle is dropped it still includes the modifier until the stat is read
+    /// through ```stat.value()```, changed, or pruned. Cheap enough for hot paths like rendering
+    /// ```
+    /// # #[cfg(not(feature = "manual-pruning"))]
+    /// # {
+    /// # use game_stat::prelude::*;
+    /// let mut armor: Stat<2> = Stat::new(10.0);
+    /// let handle = armor.add_modifier(StatModifier::Flat(5.0));
+    /// drop(handle);
+    /// assert_eq!(armor.cached_value(), 15.0);
+    /// assert_eq!(armor.value(), 10.0);
+    /// # }
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn cached_value(&self) -> f32 {
+        *S::borrow_cell(&self.value)
+    }
+
+    /// Returns the value rounded to the nearest whole number, saturating at the bounds of `T`
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut stat: Stat<2> = Stat::new(300.0);
+    /// assert_eq!(stat.value_as::<u8>(), 255);
+    /// let _handle = stat.add_modifier(StatModifier::PercentMultiply(0.1387));
+    /// assert_eq!(stat.value_as::<i16>(), 42);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn value_as<T: FromStatValue>(&self) -> T {
+        T::from_stat_value(self.value())
+    }
+
+    /// Returns the values ```stat.value()``` read after they changed, oldest first. Changes in between reads
+    /// (config setters, integrated modifiers, ...) never show up, only the value the stat settled on.
+    /// The latest [`HISTORY_CAPACITY`] values are kept
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut stat: Stat<2> = Stat::new(10.0);
+    /// let _handle = stat.add_modifier(StatModifier::Flat(5.0));
+    /// stat.set_bounds(Some(0.0), Some(100.0));
+    /// assert_eq!(*stat.history(), [15.0]);
+    /// ```
+    /// panics if refcell is borrowed
+    #[cfg(feature = "history")]
+    pub fn history(&self) -> HistoryRef<'_, S> {
+        self.value();
+        HistoryRef(S::borrow_cell(&self.history))
+    }
+
+    /// Returns a view of the value that is updated every time the stat recalculates.
+    /// Lets UI widgets poll the value cheaply without needing access to the stat.
+    /// Like ```stat.value()```, dropped modifiers are only noticed when the stat is read or changed
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut health: Stat<2> = Stat::new(100.0);
+    /// let health_bar = health.value_cell();
+    /// let _handle = health.add_modifier(StatModifier::Flat(20.0));
+    /// assert_eq!(health_bar.get(), 120.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn value_cell(&self) -> StatValueCell<S> {
+        let value = self.value();
+        S::borrow_cell(&self.value_cell)
+            .get_or_insert_with(|| {
+                let value_cell = StatValueCell::<S>::default();
+                value_cell.set(value);
+                value_cell
+            })
+            .clone()
+    }
+
+    /// Returns the internal base_value with modifiers applied, without clamping it to the bounds. The multiplier limits and rounding still apply.
+    /// With [`Stat::set_clamp_between_phases()`] the clamping between phases still happens, only the final clamp is skipped
+    /// panics if refcell is borrowed
+    pub fn raw_value(&self) -> f32 {
+        self.update_modifiers();
+        *S::borrow_cell(&self.raw_value)
+    }
+
+    /// Returns the value along with how much the bounds clamped off, like overheal above the max health.
+    /// The spillover is positive when the max bound lowered the value, negative when the min bound raised it
+    /// and 0 without bounds (see [`Stat::set_bounds()`])
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut health: Stat<2> = Stat::new(90.0);
+    /// health.set_bounds(Some(0.0), Some(100.0));
+    /// let _potion = health.add_modifier(StatModifier::Flat(30.0));
+    /// assert_eq!(health.value_with_spillover(), (100.0, 20.0));
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn value_with_spillover(&self) -> (f32, f32) {
+        let value = self.value();
+        (value, *S::borrow_cell(&self.raw_value) - value)
+    }
+
+    /// Returns how much the value changed compared to `previous_value`, positive if it increased
+    /// panics if refcell is borrowed
+    pub fn value_delta_since(&self, previous_value: f32) -> f32 {
+        self.value() - previous_value
+    }
+
+    /// Returns how much the value changed since the last call, the first call compares against the base value.
+    /// Handy for feeding floating combat text ("+12!")
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut attack: Stat<2> = Stat::new(10.0);
+    /// let _handle = attack.add_modifier(StatModifier::Flat(12.0));
+    /// assert_eq!(attack.value_delta(), 12.0);
+    /// assert_eq!(attack.value_delta(), 0.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn value_delta(&mut self) -> f32 {
+        let value = self.value();
+        let previous_value = self.last_reported_value.replace(value);
+        value - previous_value.unwrap_or(self.base_value)
+    }
+
+    /// Returns the value clamped between `min` and `max` for this read only, without storing any bounds.
+    /// If `min` is greater than `max` they are swapped
+    /// panics if refcell is borrowed
+    pub fn value_clamped_to(&self, min: f32, max: f32) -> f32 {
+        let (min, max) = if min > max { (max, min) } else { (min, max) };
+        self.value().clamp(min, max)
+    }
+
+    /// Returns true if the value is within `epsilon` of `other`
+    /// panics if refcell is borrowed
+    pub fn approx_eq(&self, other: f32, epsilon: f32) -> bool {
+        (self.value() - other).abs() <= epsilon
+    }
+
+    /// Returns true if the value reaches `threshold`, allowing it to fall short by `epsilon`
+    /// panics if refcell is borrowed
+    pub fn is_at_least(&self, threshold: f32, epsilon: f32) -> bool {
+        self.value() >= threshold - epsilon
+    }
+
+    /// Returns how much the modifiers change the value, positive when buffed: ```stat.value() - stat.base_value```
+    /// panics if refcell is borrowed
+    pub fn net_effect(&self) -> f32 {
+        self.value() - self.base_value
+    }
+
+    /// Returns the base value plus every applying flat modifier, ignoring all other kinds, bounds and rounding.
+    /// The "before percentages" line of a tooltip breakdown
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut attack: Stat<4> = Stat::new(10.0);
+    /// let _sword = attack.add_modifier(StatModifier::Flat(5.0));
+    /// let _rage = attack.add_modifier(StatModifier::PercentAdd(0.5));
+    /// assert_eq!(attack.base_plus_flats(), 15.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn base_plus_flats(&self) -> f32 {
+        self.update_modifiers();
+        let modifiers = S::borrow_cell(&self.modifiers);
+        modifiers
+            .iter()
+            .enumerate()
+            .filter(|(index, modifier_meta)| {
+                modifier_meta.modifier.kind() == ModifierKind::Flat
+                    && modifier_meta.is_active(self.condition_value)
+                    && !Self::is_suppressed(&modifiers, *index, self.condition_value)
+            })
+            .fold(self.base_value, |value, (_, modifier_meta)| {
+                value + modifier_meta.modifier.value()
+            })
     }
 
-    /// Returns the INPUT base_value (ignores self) with modifiers applied
+    /// Returns true if the value is above the base value
+    /// panics if refcell is borrowed
+    pub fn is_buffed(&self) -> bool {
+        self.net_effect() > 0.0
+    }
+
+    /// Returns true if the value is below the base value
+    /// panics if refcell is borrowed
+    pub fn is_debuffed(&self) -> bool {
+        self.net_effect() < 0.0
+    }
+
+    /// Returns the INPUT base_value (ignores self) with modifiers applied.
+    /// The same value the stat would have after setting its base to `base_value`, multiplier limits and overrides included
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut attack: Stat<2> = Stat::new(10.0);
+    /// let _handle = attack.add_modifier(StatModifier::PercentAdd(0.5));
+    /// // what attack would be at the next level
+    /// assert_eq!(attack.value_with_base(20.0), 30.0);
+    /// assert_eq!(attack.value(), 15.0);
+    /// ```
     /// panics if refcell is borrowed
     pub fn value_with_base(&self, base_value: f32) -> f32 {
         let mut value = base_value;
         // Order the modifiers
-        let mut modifiers = borrow_cell(&self.modifiers);
-        Self::order_modifiers(&mut modifiers);
-        Self::apply_modifiers_to_value(modifiers, &mut value);
-        value
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        self.order_modifiers(&mut modifiers);
+        self.apply_modifiers_to_value(&modifiers, &mut value);
+        drop(modifiers);
+        self.limit_value(base_value, &mut value);
+        self.effective_override().unwrap_or(value)
+    }
+
+    /// Returns how much the value changes per point of base value (d value / d base).
+    /// "each point of strength adds 2.5 attack" would return 2.5
+    ///
+    /// Exact when only flat and percent modifiers are active, otherwise it's approximated around the current base value
+    /// panics if refcell is borrowed
+    pub fn base_sensitivity(&self) -> f32 {
+        self.update_modifiers();
+        // the value doesn't depend on the base at all
+        if self.effective_override().is_some() {
+            return 0.0;
+        }
+        if self.min_value.is_none()
+            && self.max_value.is_none()
+            && self.min_multiplier.is_none()
+            && self.max_multiplier.is_none()
+            && self.rounding.is_none()
+            && self.step_rounding.is_none()
+        {
+            let modifiers = S::borrow_cell(&self.modifiers);
+            let scale = modifiers
+                .iter()
+                .enumerate()
+                .filter(|(index, modifier_meta)| {
+                    modifier_meta.is_active(self.condition_value)
+                        && !Self::is_suppressed(&modifiers, *index, self.condition_value)
+                })
+                .try_fold(1.0f32, |scale, (_, modifier_meta)| {
+                    modifier_meta.modifier.scale().map(|s| scale * s)
+                });
+            if let Some(scale) = scale {
+                return scale;
+            }
+        }
+
+        // central finite difference
+        let step = 0.01 * self.base_value.abs().max(1.0);
+        let above = self.value_with_base(self.base_value + step);
+        let below = self.value_with_base(self.base_value - step);
+        (above - below) / (2.0 * step)
+    }
+
+    /// Calculates the value up to (not including) `cutoff_order`, so logic like a critical hit can change the value
+    /// before the remaining modifiers are applied through [`PartialComputation::finish()`]
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut damage: Stat<2> = Stat::new(10.0);
+    /// let _sword = damage.add_modifier_with_order(StatModifier::Flat(5.0), 0);
+    /// let _armor = damage.add_modifier_with_order(StatModifier::Flat(-3.0), 10);
+    /// let partial = damage.split_compute(10);
+    /// let critical_hit = partial.value() * 2.0;
+    /// assert_eq!(partial.finish(critical_hit), 27.0);
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn split_compute(&self, cutoff_order: i32) -> PartialComputation<'_, M, S> {
+        self.update_modifiers();
+        let mut value = self.base_value;
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        self.order_modifiers(&mut modifiers);
+        let split = modifiers.partition_point(|modifier_meta| modifier_meta.order < cutoff_order);
+        self.apply_modifier_range(&modifiers, 0..split, self.condition_value, &mut value, None);
+        PartialComputation {
+            stat: self,
+            cutoff_order,
+            value,
+        }
+    }
+
+    /// Calculates the value step by step, returning every applied modifier with the value before and after it.
+    /// Inactive and suppressed modifiers are skipped, the multiplier limits, rounding, bounds and override applied
+    /// at the end aren't part of the trace. Nothing is cached, so it always does a full calculation
+    /// ```
+    /// # use game_stat::prelude::*;
+    /// let mut damage: Stat<2> = Stat::new(10.0);
+    /// let _flat = damage.add_modifier(StatModifier::Flat(5.0));
+    /// let trace = damage.compute_trace();
+    /// assert_eq!((trace[0].value_before, trace[0].value_after), (10.0, 15.0));
+    /// ```
+    /// panics if refcell is borrowed
+    pub fn compute_trace(&self) -> Vec<TraceStep> {
+        self.update_modifiers();
+        let mut trace = Vec::new();
+        let mut value = self.base_value;
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        self.order_modifiers(&mut modifiers);
+        self.apply_modifier_range(
+            &modifiers,
+            0..modifiers.len(),
+            self.condition_value,
+            &mut value,
+            Some(&mut trace),
+        );
+        trace
+    }
+
+    /// Same as calling ```stat.value_with_base()``` for every input, replacing them in place.
+    /// The modifiers are only ordered once, which makes it faster for batches (like a multi-hit ability)
+    /// panics if refcell is borrowed
+    pub fn apply_to_slice(&self, inputs: &mut [f32]) {
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        self.order_modifiers(&mut modifiers);
+        let override_value = self.effective_override();
+        for value in inputs.iter_mut() {
+            let base_value = *value;
+            self.apply_modifiers_to_value(&modifiers, value);
+            self.limit_value(base_value, value);
+            if let Some(override_value) = override_value {
+                *value = override_value;
+            }
+        }
     }
 
     /// order modifiers and apply to base value
     /// panics if refcell is borrowed
-    fn calculate_internal_value(&self) {
+    pub(crate) fn calculate_internal_value(&self) {
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_recompute();
+        *S::borrow_cell(&self.dirty) = false;
+
         let mut value = self.base_value;
 
         // Order the modifiers
-        let mut modifiers = borrow_cell(&self.modifiers);
-        Self::order_modifiers(&mut modifiers);
-        Self::apply_modifiers_to_value(modifiers, &mut value);
-        let mut internal_value = borrow_cell(&self.value);
-        *internal_value = value;
+        let mut modifiers = S::borrow_cell(&self.modifiers);
+        self.order_modifiers(&mut modifiers);
+        self.apply_modifiers_to_value(&modifiers, &mut value);
+        drop(modifiers);
+        self.limit_multipliers_and_round(self.base_value, &mut value);
+        *S::borrow_cell(&self.raw_value) = value;
+        self.clamp(&mut value);
+        if let Some(override_value) = self.effective_override() {
+            *S::borrow_cell(&self.raw_value) = override_value;
+            value = override_value;
+        }
+        #[cfg(feature = "tracing")]
+        tracing::trace!(stat = ?(self as *const Self), value, "calculated value");
+        *S::borrow_cell(&self.value) = value;
+        // recorded on the next read, so only the value the stat settles on ends up in the history
+        #[cfg(feature = "history")]
+        {
+            S::borrow_cell(&self.history).pending = true;
+        }
+        if let Some(value_cell) = S::borrow_cell(&self.value_cell).as_ref() {
+            value_cell.set(value);
+        }
+    }
+
+    // the sequence makes every key unique, so switching the tie break back and forth gives the same result
+    fn order_modifiers(&self, modifiers: &mut [ModifierMeta<S>]) {
+        match self.tie_break {
+            TieBreak::ByInsertion => modifiers.sort_by_key(|m| (m.order, m.sequence)),
+            TieBreak::ByKind => {
+                modifiers.sort_by_key(|m| (m.order, m.modifier.kind() as u8, m.sequence))
+            }
+        }
     }
 
-    fn order_modifiers(modifiers: &mut RefMut<TinyVec<[ModifierMeta; M]>>) {
-        modifiers.sort_by(|m1, m2| m1.order.cmp(&m2.order));
+    fn apply_modifiers_to_value(&self, modifiers: &[ModifierMeta<S>], value: &mut f32) {
+        self.apply_modifier_range(
+            modifiers,
+            0..modifiers.len(),
+            self.condition_value,
+            value,
+            None,
+        );
     }
 
-    fn apply_modifiers_to_value(
-        mut modifiers: RefMut<TinyVec<[ModifierMeta; M]>>,
+    // applies only the modifiers in range, all modifiers are used to find the strongest of a group.
+    // Every applied modifier is recorded in `trace` if there is one
+    fn apply_modifier_range(
+        &self,
+        modifiers: &[ModifierMeta<S>],
+        range: std::ops::Range<usize>,
+        condition_value: Option<f32>,
         value: &mut f32,
+        trace: Option<&mut Vec<TraceStep>>,
     ) {
-        for modifier_meta in modifiers.iter_mut() {
-            if let Some(_key) = modifier_meta.owner_modifier_weak.upgrade() {
-                modifier_meta.modifier.apply(value);
+        if self.f64_accumulation {
+            let mut wide_value = *value as f64;
+            self.accumulate(modifiers, range, condition_value, &mut wide_value, trace);
+            *value = wide_value as f32;
+        } else {
+            self.accumulate(modifiers, range, condition_value, value, trace);
+        }
+    }
+
+    fn accumulate<A: Accumulator>(
+        &self,
+        modifiers: &[ModifierMeta<S>],
+        range: std::ops::Range<usize>,
+        condition_value: Option<f32>,
+        value: &mut A,
+        mut trace: Option<&mut Vec<TraceStep>>,
+    ) {
+        let mut previous_order = None;
+        // sum of the increased modifiers of the current order, applied once the order changes
+        let mut increased = None;
+        for index in range {
+            let modifier_meta = &modifiers[index];
+            if !modifier_meta.is_active(condition_value)
+                || Self::is_suppressed(modifiers, index, condition_value)
+            {
+                continue;
+            }
+            if S::strong_count(&modifier_meta.owner_modifier_weak) > 0 {
+                if let Some(order) = previous_order.filter(|order| *order != modifier_meta.order) {
+                    self.apply_increased(increased.take(), order, value, trace.as_deref_mut());
+                    if self.clamp_between_phases {
+                        value.clamp_to(self.min_value, self.max_value);
+                    }
+                }
+                previous_order = Some(modifier_meta.order);
+                if let StatModifier::Increased(v) = modifier_meta.modifier {
+                    increased = Some(increased.unwrap_or(0.0) + v);
+                    continue;
+                }
+                self.apply_step(
+                    &modifier_meta.modifier,
+                    modifier_meta.order,
+                    value,
+                    trace.as_deref_mut(),
+                );
             }
         }
+        if let Some(order) = previous_order {
+            self.apply_increased(increased, order, value, trace);
+        }
+    }
+
+    fn apply_increased<A: Accumulator>(
+        &self,
+        increased: Option<f32>,
+        order: i32,
+        value: &mut A,
+        trace: Option<&mut Vec<TraceStep>>,
+    ) {
+        if let Some(increased) = increased {
+            self.apply_step(&StatModifier::Increased(increased), order, value, trace);
+        }
+    }
+
+    // applies a single modifier followed by the step rounding
+    fn apply_step<A: Accumulator>(
+        &self,
+        modifier: &StatModifier,
+        order: i32,
+        value: &mut A,
+        trace: Option<&mut Vec<TraceStep>>,
+    ) {
+        let value_before = value.to_f32();
+        value.apply(modifier);
+        if let Some(step_rounding) = self.step_rounding {
+            value.apply_rounding(step_rounding);
+        }
+        if let Some(trace) = trace {
+            trace.push(TraceStep {
+                order,
+                kind: modifier.kind(),
+                value_before,
+                value_after: value.to_f32(),
+            });
+        }
+    }
+
+    // a grouped modifier is suppressed when a valid modifier of the same group is stronger,
+    // on a tie the one applied first wins
+    fn is_suppressed(
+        modifiers: &[ModifierMeta<S>],
+        index: usize,
+        condition_value: Option<f32>,
+    ) -> bool {
+        let modifier_meta = &modifiers[index];
+        let Some(group) = modifier_meta.extras().group else {
+            return false;
+        };
+        let strength = modifier_meta.modifier.strength();
+        modifiers.iter().enumerate().any(|(other_index, other)| {
+            let other_strength = other.modifier.strength();
+            other_index != index
+                && other.extras().group == Some(group)
+                && S::strong_count(&other.owner_modifier_weak) > 0
+                && other.is_active(condition_value)
+                && (other_strength > strength
+                    || (other_strength == strength && other_index < index))
+        })
+    }
+
+    // applies the multiplier limits, rounding and bounds to a value that had all modifiers applied
+    fn limit_value(&self, base_value: f32, value: &mut f32) {
+        self.limit_multipliers_and_round(base_value, value);
+        self.clamp(value);
+    }
+
+    // everything limit_value() does, except for the bounds
+    fn limit_multipliers_and_round(&self, base_value: f32, value: &mut f32) {
+        // a negative base flips which side the limits are on
+        let flip = base_value < 0.0;
+        if let Some(max_multiplier) = self.max_multiplier {
+            let limit = base_value * max_multiplier;
+            *value = if flip {
+                value.max(limit)
+            } else {
+                value.min(limit)
+            };
+        }
+        if let Some(min_multiplier) = self.min_multiplier {
+            let limit = base_value * min_multiplier;
+            *value = if flip {
+                value.min(limit)
+            } else {
+                value.max(limit)
+            };
+        }
+        if let Some(rounding) = self.rounding {
+            *value = rounding.apply(*value);
+        }
+    }
+
+    fn clamp(&self, value: &mut f32) {
+        value.clamp_to(self.min_value, self.max_value);
     }
 }
@@ -0,0 +1,64 @@
+use crate::modifier::StatModifier;
+use crate::ref_count::{DefaultStrategy, RefCountStrategy};
+use crate::stat::{Stat, StatModifierHandle};
+
+/// The operations of a [`Stat`] that don't depend on its capacity,
+/// so stats with a different `M` can be stored together as ```Box<dyn StatLike>```
+///
+/// ```
+/// # use game_stat::prelude::*;
+/// # use game_stat::StatLike;
+/// let stats: Vec<Box<dyn StatLike>> = vec![Box::new(Stat::<2>::new(10.0)), Box::new(Stat::<8>::new(5.0))];
+/// let total: f32 = stats.iter().map(|stat| stat.value()).sum();
+/// assert_eq!(total, 15.0);
+/// ```
+pub trait StatLike<S: RefCountStrategy = DefaultStrategy> {
+    /// see [`Stat::value()`]
+    fn value(&self) -> f32;
+    fn base_value(&self) -> f32;
+    /// see [`Stat::add_modifier()`]
+    fn add_modifier(&mut self, modifier: StatModifier) -> StatModifierHandle<S>;
+    /// see [`Stat::add_modifier_with_order()`]
+    fn add_modifier_with_order(
+        &mut self,
+        modifier: StatModifier,
+        order: i32,
+    ) -> StatModifierHandle<S>;
+    /// see [`Stat::len()`]
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// how many modifiers the stat holds on the stack, see [`Stat::CAP`]
+    fn capacity(&self) -> usize;
+}
+
+impl<const M: usize, S: RefCountStrategy> StatLike<S> for Stat<M, S> {
+    fn value(&self) -> f32 {
+        Stat::value(self)
+    }
+
+    fn base_value(&self) -> f32 {
+        self.base_value
+    }
+
+    fn add_modifier(&mut self, modifier: StatModifier) -> StatModifierHandle<S> {
+        Stat::add_modifier(self, modifier)
+    }
+
+    fn add_modifier_with_order(
+        &mut self,
+        modifier: StatModifier,
+        order: i32,
+    ) -> StatModifierHandle<S> {
+        Stat::add_modifier_with_order(self, modifier, order)
+    }
+
+    fn len(&self) -> usize {
+        Stat::len(self)
+    }
+
+    fn capacity(&self) -> usize {
+        M
+    }
+}
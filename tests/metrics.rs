@@ -0,0 +1,43 @@
+// kept in its own test binary, the recompute counter is global and other tests would bump it
+#![cfg(feature = "metrics")]
+use game_stat::metrics;
+use game_stat::prelude::*;
+
+#[test]
+fn recompute_count() {
+    metrics::reset_recompute_count();
+    let mut stat: Stat<2> = Stat::new(10f32);
+    assert_eq!(metrics::recompute_count(), 0);
+
+    // every add recalculates once
+    let modifier_1 = stat.add_modifier(StatModifier::Flat(1.0));
+    let _modifier_2 = stat.add_modifier(StatModifier::Flat(1.0));
+    assert_eq!(metrics::recompute_count(), 2);
+
+    // reading an up to date value is free
+    assert_eq!(stat.value(), 12f32);
+    assert_eq!(stat.value(), 12f32);
+    assert_eq!(metrics::recompute_count(), 2);
+
+    // a dropped modifier recalculates on the next read, only once (removing it does with manual-pruning)
+    #[cfg(feature = "manual-pruning")]
+    assert!(stat.remove_modifier(&modifier_1));
+    drop(modifier_1);
+    assert_eq!(stat.value(), 11f32);
+    assert_eq!(stat.value(), 11f32);
+    assert_eq!(metrics::recompute_count(), 3);
+
+    // a builder calculates once, no matter how much it configures
+    metrics::reset_recompute_count();
+    let stat = Stat::<2>::builder()
+        .base(10f32)
+        .bounds(0f32, 100f32)
+        .max_multiplier(3f32)
+        .rounding(game_stat::Rounding::Floor)
+        .build();
+    assert_eq!(stat.value(), 10f32);
+    assert_eq!(metrics::recompute_count(), 1);
+
+    metrics::reset_recompute_count();
+    assert_eq!(metrics::recompute_count(), 0);
+}
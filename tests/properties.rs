@@ -0,0 +1,85 @@
+// random sequences of operations, checking the cached value against a calculation from scratch after every step
+use game_stat::prelude::*;
+use proptest::prelude::*;
+
+#[derive(Clone, Debug)]
+enum Operation {
+    Add(StatModifier, i32),
+    AddTimed(StatModifier, f32),
+    Drop(usize),
+    ChangeBase(f32),
+    Tick(f32),
+    SetBounds(Option<f32>, Option<f32>),
+    Prune,
+}
+
+fn modifier() -> impl Strategy<Value = StatModifier> {
+    prop_oneof![
+        (-100f32..100f32).prop_map(StatModifier::Flat),
+        (-1f32..2f32).prop_map(StatModifier::PercentAdd),
+        (0f32..3f32).prop_map(StatModifier::PercentMultiply),
+        (-50f32..50f32).prop_map(StatModifier::Floor),
+        (-50f32..200f32).prop_map(StatModifier::Ceil),
+        (0f32..0.1f32).prop_map(StatModifier::Diminishing),
+    ]
+}
+
+fn operation() -> impl Strategy<Value = Operation> {
+    prop_oneof![
+        (modifier(), -3i32..5).prop_map(|(modifier, order)| Operation::Add(modifier, order)),
+        (modifier(), 0f32..3f32)
+            .prop_map(|(modifier, duration)| Operation::AddTimed(modifier, duration)),
+        (0usize..16).prop_map(Operation::Drop),
+        (-100f32..100f32).prop_map(Operation::ChangeBase),
+        (0f32..2f32).prop_map(Operation::Tick),
+        (
+            proptest::option::of(-100f32..0f32),
+            proptest::option::of(0f32..100f32)
+        )
+            .prop_map(|(min, max)| Operation::SetBounds(min, max)),
+        Just(Operation::Prune),
+    ]
+}
+
+fn same_value(a: f32, b: f32) -> bool {
+    a.to_bits() == b.to_bits() || (a.is_nan() && b.is_nan())
+}
+
+proptest! {
+    #[test]
+    fn value_matches_recompute(operations in proptest::collection::vec(operation(), 1..40)) {
+        let mut stat: Stat<4> = Stat::new(10f32);
+        let mut handles = Vec::new();
+        for operation in operations {
+            match operation {
+                Operation::Add(modifier, order) => {
+                    handles.push(stat.add_modifier_with_order(modifier, order))
+                }
+                Operation::AddTimed(modifier, duration) => {
+                    handles.push(stat.add_modifier_with_duration(modifier, duration))
+                }
+                Operation::Drop(index) => {
+                    if !handles.is_empty() {
+                        handles.remove(index % handles.len());
+                    }
+                }
+                Operation::ChangeBase(base_value) => *stat.base_value_mut() = base_value,
+                Operation::Tick(delta_time) => {
+                    stat.tick(delta_time);
+                }
+                Operation::SetBounds(min, max) => stat.set_bounds(min, max),
+                Operation::Prune => {
+                    stat.prune();
+                }
+            }
+            let value = stat.value();
+            let fresh_value = stat.recompute_from_scratch();
+            prop_assert!(
+                same_value(value, fresh_value),
+                "value {} doesn't match the recalculated value {}",
+                value,
+                fresh_value
+            );
+        }
+    }
+}
@@ -0,0 +1,37 @@
+// kept in its own test binary, the recompute counter is global and other tests would bump it
+#![cfg(feature = "metrics")]
+use game_stat::metrics;
+use game_stat::prelude::*;
+use game_stat::StatSheet;
+
+#[test]
+fn sheet_recomputes_queried_stats() {
+    let mut sheet: StatSheet<&str, 2> = StatSheet::new();
+    sheet.insert("attack", Stat::new(10f32));
+    sheet.insert("defense", Stat::new(10f32));
+    sheet.insert("speed", Stat::new(10f32));
+    metrics::reset_recompute_count();
+
+    let _modifier_attack = sheet
+        .add_modifier("attack", StatModifier::Flat(1.0))
+        .unwrap();
+    let _modifier_defense = sheet
+        .add_modifier("defense", StatModifier::Flat(1.0))
+        .unwrap();
+    let _modifier_speed = sheet
+        .add_modifier("speed", StatModifier::Flat(1.0))
+        .unwrap();
+    assert_eq!(metrics::recompute_count(), 0);
+
+    // only the queried stat recalculates
+    assert_eq!(sheet.get("attack").unwrap().value(), 11f32);
+    assert_eq!(sheet.get("attack").unwrap().value(), 11f32);
+    assert_eq!(metrics::recompute_count(), 1);
+
+    // the sweep skips stats that are up to date
+    sheet.recompute_dirty();
+    assert_eq!(metrics::recompute_count(), 3);
+    sheet.recompute_dirty();
+    assert_eq!(metrics::recompute_count(), 3);
+    assert_eq!(sheet.get("speed").unwrap().value(), 11f32);
+}
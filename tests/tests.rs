@@ -1,4 +1,16 @@
 use game_stat::prelude::*;
+use game_stat::{ModifierKind, RefCountStrategy, StatSheet, StatSheetError};
+
+// drops the handle, with manual-pruning the modifier has to be removed explicitly as well
+fn remove_modifier<const M: usize, S: RefCountStrategy>(
+    stat: &mut Stat<M, S>,
+    handle: StatModifierHandle<S>,
+) {
+    #[cfg(feature = "manual-pruning")]
+    assert!(stat.remove_modifier(&handle));
+    let _ = stat;
+    drop(handle);
+}
 
 #[test]
 fn base_value() {
@@ -6,6 +18,7 @@ fn base_value() {
     assert!(stat.value() == 8f32);
 }
 
+#[cfg(not(feature = "manual-pruning"))]
 #[test]
 fn flat_modifier() {
     let mut stat: Stat<3> = Stat::new(8f32);
@@ -16,6 +29,7 @@ fn flat_modifier() {
     assert!(stat.value() == 8f32);
 }
 
+#[cfg(not(feature = "manual-pruning"))]
 #[test]
 fn percent_add_modifier() {
     let mut stat: Stat<3> = Stat::new(10f32);
@@ -26,6 +40,7 @@ fn percent_add_modifier() {
     assert!(stat.value() == 10f32);
 }
 
+#[cfg(not(feature = "manual-pruning"))]
 #[test]
 fn percent_multiply_modifier() {
     let mut stat: Stat<3> = Stat::new(10f32);
@@ -36,6 +51,7 @@ fn percent_multiply_modifier() {
     assert!(stat.value() == 10f32);
 }
 
+#[cfg(not(feature = "manual-pruning"))]
 #[test]
 fn all_modifiers() {
     let mut stat: Stat<3> = Stat::new(10f32);
@@ -75,6 +91,7 @@ fn internally_move_to_heap() {
     assert!(stat.value() == 4f32);
 }
 
+#[cfg(not(feature = "manual-pruning"))]
 #[test]
 // ensure that when we remove modifier and add new ones, they will be valid
 fn array_cleanup() {
@@ -86,6 +103,7 @@ fn array_cleanup() {
     assert!(stat.value() == 1f32);
 }
 
+#[cfg(not(feature = "manual-pruning"))]
 #[test]
 fn all() {
     let mut stat: Stat<2> = Stat::new(0f32);
@@ -130,7 +148,9 @@ fn integrated_modifiers_overflow() {
     assert_eq!(stat.value_with_integrated_modifiers(&other_stat), 40f32);
 }
 
+#[cfg(not(feature = "manual-pruning"))]
 #[test]
+#[allow(clippy::vec_init_then_push)]
 fn integrated_modifiers_dropped_later() {
     let mut stat: Stat<1> = Stat::new(10f32);
     let _modifier_key = stat.add_modifier(StatModifier::Flat(10f32));
@@ -152,6 +172,7 @@ fn other_base_value() {
     assert_eq!(stat.value_with_base(0.0), 10f32);
 }
 
+#[cfg(not(feature = "manual-pruning"))]
 #[test]
 // cautionary tale:
 // I wanted to highlight that shadowing a modifier does not drop the original value until it goes out of scope
@@ -170,28 +191,2307 @@ fn key_shadowing() {
     assert!(stat.value() == 0f32);
 }
 
-#[cfg(feature = "sync")]
 #[test]
-pub fn multithreaded_environment() {
-    use std::sync::{Arc, Mutex};
-    use std::thread::*;
+fn modifier_defs_round_trip() {
+    let mut stat: Stat<3> = Stat::new(10f32);
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(10f32));
+    let _modifier_multiply = stat.add_modifier_with_order(StatModifier::PercentMultiply(2.0), 5);
+    let _modifier_tagged = stat.add_modifier_with_tag(StatModifier::Flat(0f32), 7);
+    let dropped = stat.add_modifier(StatModifier::Flat(100f32));
+    remove_modifier(&mut stat, dropped);
 
-    let stat = Arc::new(Mutex::new(Stat::<2>::new(0.0f32)));
-    let stat_thread_1 = stat.clone();
-    let stat_thread_2 = stat.clone();
-    let handle_1 = spawn(move || {
-        let _modifier_handle = stat_thread_1
-            .lock()
-            .unwrap()
-            .add_modifier(StatModifier::Flat(1.0));
-    });
-    let handle_2 = spawn(move || {
-        let _modifier_handle = stat_thread_2
-            .lock()
-            .unwrap()
-            .add_modifier(StatModifier::Flat(1.0));
+    let defs = stat.into_modifier_defs();
+    assert_eq!(defs.len(), 3);
+    assert!(defs.iter().any(|def| def.order == 5));
+    assert!(defs.iter().any(|def| def.tag == Some(7)));
+
+    let (mut other_stat, handles): (Stat<3>, _) = Stat::from_base_and_defs(5f32, defs);
+    assert_eq!(handles.len(), 3);
+    assert_eq!(other_stat.value(), 30f32);
+    assert_eq!(other_stat.count_by_tag(7), 1);
+    for handle in handles {
+        remove_modifier(&mut other_stat, handle);
+    }
+    assert_eq!(other_stat.value(), 5f32);
+}
+
+#[test]
+fn into_smaller_capacity() {
+    let mut stat: Stat<8> = Stat::new(10f32);
+    let _modifier_1 = stat.add_modifier(StatModifier::Flat(10f32));
+    let _modifier_2 = stat.add_modifier(StatModifier::PercentMultiply(2.0));
+
+    let stat: Stat<2> = stat.into_capacity().unwrap();
+    assert_eq!(stat.value(), 40f32);
+}
+
+#[test]
+fn into_larger_capacity() {
+    let mut stat: Stat<1> = Stat::new(10f32);
+    let _modifier_1 = stat.add_modifier(StatModifier::Flat(10f32));
+
+    let mut stat: Stat<4> = stat.into_capacity().unwrap();
+    let _modifier_2 = stat.add_modifier(StatModifier::PercentMultiply(2.0));
+    assert_eq!(stat.value(), 40f32);
+}
+
+#[cfg(not(feature = "manual-pruning"))]
+#[test]
+// the weak reference to the handle moves along with the modifier, so the old handles stay in control
+fn into_capacity_keeps_handles() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    let modifier_1 = stat.add_modifier(StatModifier::Flat(10f32));
+    let _modifier_2 = stat.add_modifier(StatModifier::Flat(5f32));
+
+    let stat: Stat<2> = stat.into_capacity().unwrap();
+    assert_eq!(stat.value(), 25f32);
+    drop(modifier_1);
+    assert_eq!(stat.value(), 15f32);
+    assert_eq!(stat.len(), 1);
+}
+
+#[test]
+fn into_capacity_too_small() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    let _modifier_1 = stat.add_modifier(StatModifier::Flat(10f32));
+    let _modifier_2 = stat.add_modifier(StatModifier::Flat(10f32));
+    // removed modifiers don't count against the new capacity
+    let modifier_3 = stat.add_modifier(StatModifier::Flat(10f32));
+    remove_modifier(&mut stat, modifier_3);
+
+    let result: Result<Stat<1>, _> = stat.into_capacity();
+    let stat = result.unwrap_err();
+    assert_eq!(stat.value(), 30f32);
+    assert!(stat.into_capacity::<2>().is_ok());
+}
+
+#[test]
+fn bounds() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    stat.set_bounds(Some(0f32), Some(20f32));
+    let modifier = stat.add_modifier(StatModifier::Flat(50f32));
+    assert_eq!(stat.value(), 20f32);
+    remove_modifier(&mut stat, modifier);
+
+    let _modifier = stat.add_modifier(StatModifier::Flat(-50f32));
+    assert_eq!(stat.value(), 0f32);
+    stat.set_bounds(None, None);
+    assert_eq!(stat.value(), -40f32);
+}
+
+#[test]
+fn clamp_at_end_only() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    stat.set_bounds(None, Some(20f32));
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(20f32));
+    let _modifier_multiply = stat.add_modifier(StatModifier::PercentMultiply(0.5));
+    // 10 + 20 = 30, 30 * 0.5 = 15
+    assert_eq!(stat.value(), 15f32);
+}
+
+#[test]
+fn clamp_between_phases() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    stat.set_bounds(None, Some(20f32));
+    stat.set_clamp_between_phases(true);
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(20f32));
+    let _modifier_multiply = stat.add_modifier(StatModifier::PercentMultiply(0.5));
+    // 10 + 20 = 30 which is clamped to 20, 20 * 0.5 = 10
+    assert_eq!(stat.value(), 10f32);
+}
+
+#[test]
+fn raw_value() {
+    let mut stat: Stat<2> = Stat::new(100f32);
+    stat.set_bounds(None, Some(100f32));
+    let _modifier = stat.add_modifier(StatModifier::PercentAdd(0.5));
+    assert_eq!(stat.value(), 100f32);
+    assert_eq!(stat.raw_value(), 150f32);
+}
+
+#[cfg(not(feature = "manual-pruning"))]
+#[test]
+fn dead_slots() {
+    let mut stat: Stat<3> = Stat::new(0f32);
+    let _modifier_1 = stat.add_modifier(StatModifier::Flat(1.0f32));
+    let modifier_2 = stat.add_modifier(StatModifier::Flat(1.0f32));
+    let modifier_3 = stat.add_modifier(StatModifier::Flat(1.0f32));
+    assert_eq!(stat.len(), 3);
+    assert_eq!(stat.raw_slot_count(), 3);
+
+    drop(modifier_2);
+    drop(modifier_3);
+    assert_eq!(stat.len(), 1);
+    assert_eq!(stat.raw_slot_count(), 3);
+
+    // reading the value cleans up the dropped modifiers
+    assert_eq!(stat.value(), 1f32);
+    assert_eq!(stat.len(), 1);
+    assert_eq!(stat.raw_slot_count(), 1);
+}
+
+#[test]
+fn floor_before_percent() {
+    let mut stat: Stat<2> = Stat::new(2f32);
+    let _modifier_floor = stat.add_modifier_with_order(StatModifier::Floor(10f32), 0);
+    let _modifier_percent = stat.add_modifier_with_order(StatModifier::PercentAdd(0.5), 1);
+    // 2 is raised to 10, 10 * 1.5 = 15
+    assert_eq!(stat.value(), 15f32);
+}
+
+#[test]
+fn floor_after_percent() {
+    let mut stat: Stat<2> = Stat::new(2f32);
+    let _modifier_percent = stat.add_modifier_with_order(StatModifier::PercentAdd(0.5), 1);
+    let _modifier_floor = stat.add_modifier_with_order(StatModifier::Floor(10f32), 2);
+    // 2 * 1.5 = 3, raised to 10
+    assert_eq!(stat.value(), 10f32);
+}
+
+#[test]
+fn ceil_modifier() {
+    let mut stat: Stat<2> = Stat::new(20f32);
+    let _modifier_ceil = stat.add_modifier_with_order(StatModifier::Ceil(10f32), 0);
+    let _modifier_flat = stat.add_modifier_with_order(StatModifier::Flat(5f32), 1);
+    assert_eq!(stat.value(), 15f32);
+}
+
+#[test]
+fn consuming_iterator() {
+    let mut stat: Stat<3> = Stat::new(0f32);
+    let _modifier_1 = stat.add_modifier(StatModifier::Flat(1.0f32));
+    let _modifier_2 = stat.add_modifier_with_order(StatModifier::Flat(1.0f32), 4);
+    let modifier_3 = stat.add_modifier(StatModifier::Flat(1.0f32));
+    remove_modifier(&mut stat, modifier_3);
+
+    let mut count = 0;
+    for (modifier, order) in stat {
+        assert!(matches!(modifier, StatModifier::Flat(_)));
+        assert!(order == 0 || order == 4);
+        count += 1;
+    }
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn scale_percent_modifiers() {
+    let mut stat: Stat<3> = Stat::new(10f32);
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(10f32));
+    let _modifier_percent_add = stat.add_modifier(StatModifier::PercentAdd(1.0));
+    let _modifier_percent_multiply = stat.add_modifier(StatModifier::PercentMultiply(3.0));
+    // (10 + 10) * 2 * 3
+    assert_eq!(stat.value(), 120f32);
+
+    stat.scale_percent_modifiers(0.5);
+    // (10 + 10) * 1.5 * 2
+    assert_eq!(stat.value(), 60f32);
+
+    // scaling compounds
+    stat.scale_percent_modifiers(0.5);
+    // (10 + 10) * 1.25 * 1.5
+    assert_eq!(stat.value(), 37.5f32);
+}
+
+#[test]
+fn base_sensitivity() {
+    let mut stat: Stat<3> = Stat::new(10f32);
+    assert_eq!(stat.base_sensitivity(), 1f32);
+
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(100f32));
+    let _modifier_percent_add = stat.add_modifier(StatModifier::PercentAdd(0.25));
+    let _modifier_percent_multiply = stat.add_modifier(StatModifier::PercentMultiply(2.0));
+    assert_eq!(stat.base_sensitivity(), 2.5f32);
+
+    // an override ignores the base value
+    stat.set_override(Some(50f32));
+    assert_eq!(stat.base_sensitivity(), 0f32);
+    stat.set_override(None);
+    let frozen = stat.push_override(0f32);
+    assert_eq!(stat.base_sensitivity(), 0f32);
+    drop(frozen);
+    assert_eq!(stat.base_sensitivity(), 2.5f32);
+}
+
+#[test]
+fn base_sensitivity_approximated() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let _modifier_floor = stat.add_modifier(StatModifier::Floor(0f32));
+    let _modifier_percent_add = stat.add_modifier(StatModifier::PercentAdd(1.5));
+    assert!((stat.base_sensitivity() - 2.5f32).abs() < 0.001);
+
+    // stuck at the floor, the base doesn't matter
+    stat.base_value = -10f32;
+    assert!(stat.base_sensitivity().abs() < 0.001);
+}
+
+#[test]
+fn sheet_modifier_budget() {
+    let mut sheet: StatSheet<&str, 4> = StatSheet::with_modifier_budget(3);
+    sheet.insert("attack", Stat::new(10f32));
+    sheet.insert("defense", Stat::new(10f32));
+
+    let modifier_1 = sheet
+        .add_modifier("attack", StatModifier::Flat(1.0))
+        .unwrap();
+    let _modifier_2 = sheet
+        .add_modifier("attack", StatModifier::Flat(1.0))
+        .unwrap();
+    let _modifier_3 = sheet
+        .add_modifier("defense", StatModifier::Flat(1.0))
+        .unwrap();
+    assert_eq!(sheet.modifier_count(), 3);
+
+    // both stats have room, but the sheet doesn't
+    assert_eq!(
+        sheet
+            .add_modifier("defense", StatModifier::Flat(1.0))
+            .unwrap_err(),
+        StatSheetError::ModifierBudgetExceeded
+    );
+
+    remove_modifier(sheet.get_mut("attack").unwrap(), modifier_1);
+    let _modifier_4 = sheet
+        .add_modifier("defense", StatModifier::Flat(1.0))
+        .unwrap();
+    assert_eq!(sheet.get("defense").unwrap().value(), 12f32);
+    // a full sheet still reports a stat it doesn't have
+    assert_eq!(
+        sheet
+            .add_modifier("speed", StatModifier::Flat(1.0))
+            .unwrap_err(),
+        StatSheetError::UnknownStat
+    );
+}
+
+#[test]
+fn sheet_unknown_stat() {
+    let mut sheet: StatSheet<&str, 4> = StatSheet::new();
+    assert_eq!(
+        sheet
+            .add_modifier("speed", StatModifier::Flat(1.0))
+            .unwrap_err(),
+        StatSheetError::UnknownStat
+    );
+}
+
+#[test]
+fn config_hash() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let _modifier_1 = stat.add_modifier(StatModifier::Flat(5f32));
+    let hash = stat.config_hash();
+    assert_eq!(stat.value(), 15f32);
+    assert_eq!(stat.config_hash(), hash);
+
+    let modifier_2 = stat.add_modifier(StatModifier::Flat(5f32));
+    let hash_with_modifier_2 = stat.config_hash();
+    assert_ne!(hash_with_modifier_2, hash);
+
+    // same values, different kind
+    drop(modifier_2);
+    let _modifier_2 = stat.add_modifier_with_order(StatModifier::PercentAdd(5f32), 0);
+    assert_ne!(stat.config_hash(), hash_with_modifier_2);
+
+    // overrides
+    let hash = stat.config_hash();
+    stat.set_override(Some(1f32));
+    assert_ne!(stat.config_hash(), hash);
+    stat.set_override(None);
+    assert_eq!(stat.config_hash(), hash);
+    let frozen = stat.push_override(0f32);
+    assert_ne!(stat.config_hash(), hash);
+    drop(frozen);
+    assert_eq!(stat.config_hash(), hash);
+
+    // a delayed modifier activating
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let _modifier_delayed = stat.add_modifier_with_delay(StatModifier::Flat(5f32), 2f32, None);
+    let hash = stat.config_hash();
+    stat.tick(1f32);
+    assert_eq!(stat.config_hash(), hash);
+    stat.tick(1f32);
+    assert_eq!(stat.value(), 15f32);
+    assert_ne!(stat.config_hash(), hash);
+
+    // conditions
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let modifier_below =
+        stat.add_conditional_modifier(StatModifier::Flat(5f32), game_stat::Condition::Below(0.5));
+    let hash = stat.config_hash();
+    drop(modifier_below);
+    let _modifier_at_least =
+        stat.add_conditional_modifier(StatModifier::Flat(5f32), game_stat::Condition::AtLeast(0.5));
+    assert_ne!(stat.config_hash(), hash);
+}
+
+#[test]
+fn charges() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    // no charges, nothing to add
+    assert!(stat
+        .add_modifier_with_charges(StatModifier::Flat(5f32), 0)
+        .is_none());
+    assert_eq!(stat.raw_slot_count(), 0);
+    let modifier_charged = stat
+        .add_modifier_with_charges(StatModifier::Flat(5f32), 3)
+        .unwrap();
+    let modifier_flat = stat.add_modifier(StatModifier::Flat(1f32));
+    assert_eq!(stat.value(), 16f32);
+
+    assert_eq!(stat.consume_charge(&modifier_charged), Some(2));
+    assert_eq!(stat.consume_charge(&modifier_charged), Some(1));
+    assert_eq!(stat.value(), 16f32);
+    assert_eq!(stat.consume_charge(&modifier_charged), Some(0));
+    assert_eq!(stat.value(), 11f32);
+    assert_eq!(stat.len(), 1);
+
+    // the charged modifier is gone, and a plain modifier has no charges
+    assert_eq!(stat.consume_charge(&modifier_charged), None);
+    assert_eq!(stat.consume_charge(&modifier_flat), None);
+}
+
+#[test]
+fn new_with_modifiers() {
+    let (mut stat, handles) = Stat::<3>::new_with_modifiers(
+        10f32,
+        [
+            StatModifier::PercentMultiply(2.0),
+            StatModifier::Flat(10f32),
+            StatModifier::PercentAdd(0.5),
+        ],
+    );
+    // the default order applies, not the order of the array
+    assert_eq!(stat.value(), 60f32);
+    for handle in handles {
+        remove_modifier(&mut stat, handle);
+    }
+    assert_eq!(stat.value(), 10f32);
+}
+
+#[test]
+fn effective_order() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let modifier_1 = stat.add_modifier(StatModifier::PercentMultiply(2.0));
+    let modifier_2 = stat.add_modifier_with_order(StatModifier::Flat(1.0), 7);
+    assert_eq!(
+        stat.effective_order(&modifier_1),
+        Some(StatModifier::PercentMultiply(2.0).default_order())
+    );
+    assert_eq!(stat.effective_order(&modifier_2), Some(7));
+
+    let other_stat: Stat<2> = Stat::new(10f32);
+    assert_eq!(other_stat.effective_order(&modifier_1), None);
+}
+
+#[test]
+fn value_delta_since() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let previous = stat.value();
+    let modifier = stat.add_modifier(StatModifier::Flat(12f32));
+    assert_eq!(stat.value_delta_since(previous), 12f32);
+    remove_modifier(&mut stat, modifier);
+    assert_eq!(stat.value_delta_since(previous), 0f32);
+}
+
+#[test]
+fn value_delta() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    assert_eq!(stat.value_delta(), 0f32);
+    let modifier_1 = stat.add_modifier(StatModifier::Flat(12f32));
+    assert_eq!(stat.value_delta(), 12f32);
+    assert_eq!(stat.value_delta(), 0f32);
+
+    let _modifier_2 = stat.add_modifier(StatModifier::Flat(3f32));
+    remove_modifier(&mut stat, modifier_1);
+    assert_eq!(stat.value_delta(), -9f32);
+}
+
+#[test]
+fn value_override() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let _modifier_1 = stat.add_modifier(StatModifier::Flat(5f32));
+    stat.set_override(Some(100f32));
+    assert_eq!(stat.value(), 100f32);
+
+    // modifiers keep changing underneath the override
+    let _modifier_2 = stat.add_modifier(StatModifier::Flat(5f32));
+    assert_eq!(stat.value(), 100f32);
+
+    stat.set_override(None);
+    assert_eq!(stat.value(), 20f32);
+}
+
+#[test]
+fn reorder_modifiers() {
+    let mut stat: Stat<3> = Stat::new(10f32);
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(10f32));
+    let _modifier_multiply = stat.add_modifier(StatModifier::PercentMultiply(2.0));
+    assert_eq!(stat.value(), 40f32);
+
+    for mut modifier in stat.modifiers_mut().iter_mut() {
+        if let StatModifier::PercentMultiply(_) = modifier.modifier() {
+            modifier.set_order(-1);
+        }
+    }
+    // 10 * 2 + 10
+    assert_eq!(stat.value(), 30f32);
+    let orders: Vec<i32> = stat
+        .modifiers_mut()
+        .iter_mut()
+        .map(|modifier| modifier.order())
+        .collect();
+    assert_eq!(orders, vec![-1, 0]);
+}
+
+#[test]
+fn modifiers_in_applied_order() {
+    let mut stat: Stat<3> = Stat::new(10f32);
+    let _modifier_flat = stat.add_modifier_with_order(StatModifier::Flat(10f32), 0);
+    let _modifier_multiply = stat.add_modifier_with_order(StatModifier::PercentMultiply(2.0), 1);
+    for mut modifier in stat.modifiers_mut().iter_mut() {
+        modifier.set_order(-modifier.order());
+    }
+    let orders: Vec<i32> = stat.modifiers().map(|info| info.order).collect();
+    assert_eq!(orders, vec![-1, 0]);
+}
+
+#[test]
+fn capacity_const() {
+    const CAPACITY: usize = Stat::<5>::CAP;
+    let companion = [0u8; Stat::<5>::CAP];
+    assert_eq!(companion.len(), 5);
+    assert_eq!(CAPACITY, 5);
+}
+
+#[test]
+fn describe() {
+    let mut stat: Stat<3> = Stat::new(10f32);
+    stat.set_bounds(Some(0f32), None);
+    let _modifier_multiply = stat.add_modifier(StatModifier::PercentMultiply(2.0));
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(5f32));
+    let dropped = stat.add_modifier(StatModifier::Flat(100f32));
+    remove_modifier(&mut stat, dropped);
+    assert_eq!(
+        stat.describe(),
+        "base: 10\n\
+         bounds: Some(0.0)..None\n\
+         Flat(5.0) order: 0\n\
+         PercentMultiply(2.0) order: 2\n\
+         value: 30\n"
+    );
+}
+
+#[test]
+fn diminishing_returns() {
+    let mut stat: Stat<2> = Stat::new(100f32);
+    let _modifier = stat.add_modifier(StatModifier::Diminishing(0.01));
+    assert_eq!(stat.value(), 50f32);
+    assert_eq!(stat.value_with_base(300f32), 75f32);
+
+    // always increasing, but every step gives less
+    let mut previous_value = stat.value_with_base(0f32);
+    let mut previous_gain = f32::MAX;
+    for base in (10..1000).step_by(10) {
+        let value = stat.value_with_base(base as f32);
+        let gain = value - previous_value;
+        assert!(gain > 0f32);
+        assert!(gain < previous_gain);
+        previous_value = value;
+        previous_gain = gain;
+    }
+}
+
+#[cfg(not(feature = "manual-pruning"))]
+#[test]
+fn add_modifier_reporting_reuse() {
+    let mut stat: Stat<2> = Stat::new(0f32);
+    let (modifier_1, reused) = stat.add_modifier_reporting_reuse(StatModifier::Flat(1f32));
+    assert!(!reused);
+
+    drop(modifier_1);
+    let (_modifier_2, reused) = stat.add_modifier_reporting_reuse(StatModifier::Flat(2f32));
+    assert!(reused);
+    let (_modifier_3, reused) = stat.add_modifier_reporting_reuse(StatModifier::Flat(3f32));
+    assert!(!reused);
+    assert_eq!(stat.value(), 5f32);
+}
+
+#[test]
+fn approximate_comparisons() {
+    let mut stat: Stat<2> = Stat::new(0.1f32);
+    let _modifier = stat.add_modifier(StatModifier::Flat(0.2f32));
+    assert!(stat.approx_eq(0.3, 0.0001));
+    assert!(!stat.approx_eq(0.31, 0.0001));
+
+    assert!(stat.is_at_least(0.3, 0.0001));
+    assert!(stat.is_at_least(0.2, 0.0));
+    assert!(!stat.is_at_least(0.31, 0.0001));
+    assert!(stat.is_at_least(0.31, 0.01));
+}
+
+#[test]
+fn stat_from_defs_macro() {
+    fn capacity<const M: usize>(_stat: &Stat<M>) -> usize {
+        M
+    }
+
+    let (stat, handles) = game_stat::stat_from_defs!(
+        10f32,
+        [
+            ModifierDef::from(StatModifier::Flat(10f32)),
+            ModifierDef::new(StatModifier::PercentAdd(1.0), 1),
+            ModifierDef::new(StatModifier::Flat(-5f32), 2),
+        ]
+    );
+    assert_eq!(capacity(&stat), 3);
+    assert_eq!(handles.len(), 3);
+    assert_eq!(stat.value(), 35f32);
+}
+
+#[test]
+fn linked_modifiers() {
+    let mut attack: Stat<2> = Stat::new(10f32);
+    let mut defense: Stat<2> = Stat::new(10f32);
+    let _other_modifier = defense.add_modifier(StatModifier::Flat(1f32));
+
+    let berserk = attack.add_modifier(StatModifier::PercentAdd(0.5));
+    defense.attach_modifier(&berserk, StatModifier::PercentAdd(-0.5));
+    assert_eq!(attack.value(), 15f32);
+    assert_eq!(defense.value(), 5.5f32);
+
+    #[cfg(feature = "manual-pruning")]
+    defense.remove_modifier(&berserk);
+    remove_modifier(&mut attack, berserk);
+    assert_eq!(attack.value(), 10f32);
+    assert_eq!(defense.value(), 11f32);
+}
+
+#[test]
+fn value_clamped_to() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let _modifier = stat.add_modifier(StatModifier::Flat(5f32));
+    assert_eq!(stat.value_clamped_to(0f32, 20f32), 15f32);
+    assert_eq!(stat.value_clamped_to(20f32, 30f32), 20f32);
+    assert_eq!(stat.value_clamped_to(0f32, 10f32), 10f32);
+    // min and max are swapped
+    assert_eq!(stat.value_clamped_to(10f32, 0f32), 10f32);
+    // nothing is stored
+    assert_eq!(stat.value(), 15f32);
+}
+
+#[test]
+fn raw_round_trip() {
+    let mut stat: Stat<3> = Stat::new(10f32);
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(10f32));
+    let _modifier_floor = stat.add_modifier_with_order(StatModifier::Floor(50f32), 4);
+    let (base_value, raw_modifiers) = stat.to_raw();
+    assert_eq!(base_value, 10f32);
+    assert_eq!(raw_modifiers, vec![(0, 10f32, 0), (3, 50f32, 4)]);
+
+    let (other_stat, _handles): (Stat<3>, _) = Stat::from_raw(base_value, &raw_modifiers).unwrap();
+    assert_eq!(other_stat.value(), stat.value());
+    assert_eq!(other_stat.to_raw(), (base_value, raw_modifiers));
+
+    assert!(Stat::<3>::from_raw(10f32, &[(200, 1f32, 0)]).is_none());
+}
+
+#[test]
+fn order_collision() {
+    let mut stat: Stat<3> = Stat::new(10f32);
+    let _modifier_flat = stat
+        .try_add_modifier_with_order(StatModifier::Flat(5f32), 0)
+        .unwrap();
+    // same kind is fine, flats commute
+    let _modifier_flat_2 = stat
+        .try_add_modifier_with_order(StatModifier::Flat(5f32), 0)
+        .unwrap();
+    let error = stat
+        .try_add_modifier_with_order(StatModifier::PercentMultiply(2.0), 0)
+        .unwrap_err();
+    assert_eq!(error.order, 0);
+    assert_eq!(error.existing_kind, ModifierKind::Flat);
+    assert_eq!(stat.len(), 2);
+    assert!(stat
+        .try_add_modifier_with_order(StatModifier::PercentMultiply(2.0), 1)
+        .is_ok());
+}
+
+#[test]
+fn map_modifiers() {
+    let mut stat: Stat<3> = Stat::new(10f32);
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(5f32));
+    let _modifier_flat_2 = stat.add_modifier(StatModifier::Flat(5f32));
+    let _modifier_multiply = stat.add_modifier(StatModifier::PercentMultiply(2.0));
+
+    let (mut doubled, handles) = stat.map_modifiers(|modifier| match modifier {
+        StatModifier::Flat(v) => StatModifier::Flat(v * 2.0),
+        other => *other,
     });
-    handle_1.join().unwrap();
-    handle_2.join().unwrap();
-    assert!(stat.lock().unwrap().value() == 0.0f32);
+    // (10 + 10 + 10) * 2
+    assert_eq!(doubled.value(), 60f32);
+    assert_eq!(stat.value(), 40f32);
+
+    // the new stat has its own handles
+    for handle in handles {
+        remove_modifier(&mut doubled, handle);
+    }
+    assert_eq!(doubled.value(), 10f32);
+    assert_eq!(stat.value(), 40f32);
+}
+
+#[cfg(not(feature = "manual-pruning"))]
+#[test]
+fn value_cell() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let value_cell = stat.value_cell();
+    assert_eq!(value_cell.get(), 10f32);
+
+    let modifier = stat.add_modifier(StatModifier::Flat(5f32));
+    assert_eq!(value_cell.get(), 15f32);
+
+    // the drop is noticed on the next read
+    drop(modifier);
+    assert_eq!(value_cell.get(), 15f32);
+    stat.value();
+    assert_eq!(value_cell.get(), 10f32);
+
+    // every view shares the same value
+    let other_value_cell = stat.value_cell();
+    let _modifier = stat.add_modifier(StatModifier::Flat(1f32));
+    assert_eq!(value_cell.get(), 11f32);
+    assert_eq!(other_value_cell.get(), 11f32);
+}
+
+#[cfg(not(feature = "manual-pruning"))]
+#[test]
+fn prune() {
+    let mut stat: Stat<4> = Stat::new(0f32);
+    let _modifier_1 = stat.add_modifier(StatModifier::Flat(1.0f32));
+    let modifier_2 = stat.add_modifier(StatModifier::Flat(1.0f32));
+    let modifier_3 = stat.add_modifier(StatModifier::Flat(1.0f32));
+    assert_eq!(stat.prune(), 0);
+
+    drop(modifier_2);
+    drop(modifier_3);
+    assert_eq!(stat.raw_slot_count(), 3);
+    assert_eq!(stat.prune(), 2);
+    assert_eq!(stat.raw_slot_count(), 1);
+    assert_eq!(stat.prune(), 0);
+    assert_eq!(stat.value(), 1f32);
+}
+
+#[test]
+fn errors_box_into_dyn_error() {
+    fn add_strict(stat: &mut Stat<2>) -> Result<StatModifierHandle, Box<dyn std::error::Error>> {
+        Ok(stat.try_add_modifier_with_order(StatModifier::PercentAdd(0.5), 0)?)
+    }
+    fn add_to_sheet(
+        sheet: &mut StatSheet<&str, 2>,
+    ) -> Result<StatModifierHandle, Box<dyn std::error::Error>> {
+        Ok(sheet.add_modifier("speed", StatModifier::Flat(1f32))?)
+    }
+
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let _modifier = stat.add_modifier(StatModifier::Flat(1f32));
+    let error = add_strict(&mut stat).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "order 0 is already used by a modifier of kind Flat"
+    );
+
+    let mut sheet = StatSheet::new();
+    let error = add_to_sheet(&mut sheet).unwrap_err();
+    assert_eq!(error.to_string(), "the sheet has no stat with that key");
+}
+
+#[test]
+fn apply_to_slice() {
+    let mut stat: Stat<3> = Stat::new(10f32);
+    stat.set_bounds(None, Some(100f32));
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(5f32));
+    let _modifier_percent = stat.add_modifier(StatModifier::PercentAdd(0.5));
+
+    let inputs = [0f32, 10f32, 20f32, 1000f32];
+    let mut outputs = inputs;
+    stat.apply_to_slice(&mut outputs);
+    for (input, output) in inputs.iter().zip(outputs) {
+        assert_eq!(stat.value_with_base(*input), output);
+    }
+    assert_eq!(outputs, [7.5f32, 22.5f32, 37.5f32, 100f32]);
+}
+
+#[test]
+fn max_multiplier() {
+    let mut stat: Stat<3> = Stat::new(10f32);
+    stat.set_max_multiplier(Some(3f32));
+    let _modifier_1 = stat.add_modifier(StatModifier::PercentMultiply(2.0));
+    assert_eq!(stat.value(), 20f32);
+    let _modifier_2 = stat.add_modifier(StatModifier::PercentMultiply(2.0));
+    assert_eq!(stat.value(), 30f32);
+    // the raw value only skips the bounds, the multiplier limit still applies
+    assert_eq!(stat.raw_value(), 30f32);
+}
+
+#[test]
+fn min_multiplier() {
+    let mut stat: Stat<3> = Stat::new(10f32);
+    stat.set_min_multiplier(Some(0.5f32));
+    let _modifier = stat.add_modifier(StatModifier::PercentMultiply(0.1));
+    assert_eq!(stat.value(), 5f32);
+}
+
+#[test]
+fn multiplier_negative_base() {
+    let mut stat: Stat<3> = Stat::new(-10f32);
+    stat.set_max_multiplier(Some(3f32));
+    stat.set_min_multiplier(Some(0.5f32));
+    let modifier = stat.add_modifier(StatModifier::PercentMultiply(5.0));
+    // the magnitude is capped at 3x, so it can't go below -30
+    assert_eq!(stat.value(), -30f32);
+    drop(modifier);
+
+    let _modifier = stat.add_modifier(StatModifier::PercentMultiply(0.1));
+    // and at least half the magnitude
+    assert_eq!(stat.value(), -5f32);
+}
+
+#[test]
+fn tick_reports_expired_modifiers() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    let _modifier_short = stat.add_modifier_with_duration(StatModifier::Flat(1f32), 1.0);
+    let _modifier_long = stat.add_modifier_with_duration(StatModifier::Flat(2f32), 2.5);
+    let _modifier_permanent = stat.add_modifier(StatModifier::Flat(4f32));
+    assert_eq!(stat.value(), 17f32);
+
+    assert!(stat.tick(0.5).is_empty());
+    let expired = stat.tick(0.5);
+    assert_eq!(expired.len(), 1);
+    assert!(matches!(expired[0].modifier, StatModifier::Flat(v) if v == 1f32));
+    assert_eq!(stat.value(), 16f32);
+
+    let expired = stat.tick(5.0);
+    assert_eq!(expired.len(), 1);
+    assert!(matches!(expired[0].modifier, StatModifier::Flat(v) if v == 2f32));
+    assert_eq!(stat.value(), 14f32);
+
+    // nothing is reported twice
+    assert!(stat.tick(5.0).is_empty());
+}
+
+#[test]
+fn tick_keeps_waiting_modifiers() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    let _modifier_short = stat.add_modifier_with_duration(StatModifier::Flat(1f32), 1.0);
+    // no duration left once it activates, but it's still waiting
+    let _modifier_delayed =
+        stat.add_modifier_with_delay(StatModifier::Flat(2f32), 3f32, Some(0f32));
+    assert_eq!(stat.tick(1.0).len(), 1);
+    assert_eq!(stat.len(), 1);
+
+    let expired = stat.tick(2.0);
+    assert_eq!(expired.len(), 1);
+    assert!(matches!(expired[0].modifier, StatModifier::Flat(v) if v == 2f32));
+    assert!(stat.is_empty());
+}
+
+#[cfg(not(feature = "manual-pruning"))]
+#[test]
+fn tick_ignores_dropped_modifiers() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let modifier = stat.add_modifier_with_duration(StatModifier::Flat(1f32), 1.0);
+    drop(modifier);
+    assert!(stat.tick(2.0).is_empty());
+    assert_eq!(stat.value(), 10f32);
+}
+
+#[test]
+fn insert_between_orders() {
+    let mut stat: Stat<3> = Stat::new(10f32);
+    let _modifier_flat =
+        stat.add_modifier_with_order(StatModifier::Flat(10f32), game_stat::spaced_order(10));
+    let _modifier_multiply = stat.add_modifier_with_order(
+        StatModifier::PercentMultiply(2.0),
+        game_stat::spaced_order(11),
+    );
+    assert_eq!(stat.value(), 40f32);
+
+    let between =
+        game_stat::order_between(game_stat::spaced_order(10), game_stat::spaced_order(11)).unwrap();
+    let _modifier_ceil = stat.add_modifier_with_order(StatModifier::Ceil(15f32), between);
+    // 10 + 10, capped at 15, * 2
+    assert_eq!(stat.value(), 30f32);
+
+    assert_eq!(game_stat::order_between(10, 11), None);
+    assert_eq!(game_stat::order_between(11, 10), None);
+    assert_eq!(game_stat::order_between(i32::MIN, i32::MAX), Some(-1));
+}
+
+#[test]
+fn rounding() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let _modifier = stat.add_modifier(StatModifier::PercentMultiply(1.25));
+    assert_eq!(stat.value(), 12.5);
+    stat.set_rounding(Some(game_stat::Rounding::Floor));
+    assert_eq!(stat.value(), 12f32);
+    stat.set_rounding(Some(game_stat::Rounding::Ceil));
+    assert_eq!(stat.value(), 13f32);
+    stat.set_rounding(Some(game_stat::Rounding::Round));
+    assert_eq!(stat.value(), 13f32);
+    // bounds are applied after rounding
+    stat.set_bounds(None, Some(12.5));
+    assert_eq!(stat.value(), 12.5);
+    stat.set_rounding(None);
+    stat.set_bounds(None, None);
+    assert_eq!(stat.value(), 12.5);
+}
+
+#[test]
+fn step_rounding() {
+    let mut stat: Stat<2> = Stat::new(5f32);
+    let _modifier_percent = stat.add_modifier(StatModifier::PercentAdd(0.5));
+    let _modifier_flat = stat.add_modifier_with_order(StatModifier::Flat(0.5), 5);
+    assert_eq!(stat.value(), 8f32);
+
+    // rounding at the end: floor(7.5 + 0.5)
+    stat.set_rounding(Some(game_stat::Rounding::Floor));
+    assert_eq!(stat.value(), 8f32);
+    // rounding every step: floor(floor(7.5) + 0.5)
+    stat.set_step_rounding(Some(game_stat::Rounding::Floor));
+    assert_eq!(stat.step_rounding(), Some(game_stat::Rounding::Floor));
+    assert_eq!(stat.value(), 7f32);
+    assert_eq!(stat.base_sensitivity(), 0f32);
+
+    stat.set_rounding(None);
+    stat.set_step_rounding(None);
+    assert_eq!(stat.value(), 8f32);
+}
+
+#[test]
+fn builder() {
+    let mut stat = Stat::<4>::builder()
+        .base(10f32)
+        .bounds(0f32, 100f32)
+        .clamp_between_phases(true)
+        .min_multiplier(0.5)
+        .max_multiplier(3f32)
+        .rounding(game_stat::Rounding::Floor)
+        .build();
+    assert_eq!(stat.base_value, 10f32);
+    assert_eq!(stat.bounds(), (Some(0f32), Some(100f32)));
+    assert_eq!(stat.multiplier_limits(), (Some(0.5), Some(3f32)));
+    assert_eq!(stat.rounding(), Some(game_stat::Rounding::Floor));
+    assert_eq!(stat.override_value(), None);
+
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(2.5));
+    assert_eq!(stat.value(), 12f32);
+    let _modifier_multiply = stat.add_modifier(StatModifier::PercentMultiply(10f32));
+    // 125 limited to 3x the base
+    assert_eq!(stat.value(), 30f32);
+    let _modifier_negative = stat.add_modifier(StatModifier::Flat(-1000f32));
+    // clamped to 0 before the multiply, then limited to at least half the base
+    assert_eq!(stat.value(), 5f32);
+
+    let stat = Stat::<4>::builder()
+        .base(10f32)
+        .override_value(1f32)
+        .build();
+    assert_eq!(stat.value(), 1f32);
+}
+
+#[test]
+fn linked_stats() {
+    let shield = game_stat::SharedBase::new(10f32);
+    let mut hud_shield: game_stat::LinkedStat<2> = game_stat::LinkedStat::new(&shield);
+    let mut party_shield: game_stat::LinkedStat<2> = game_stat::LinkedStat::new(&shield);
+    let _modifier_flat = party_shield.add_modifier(StatModifier::Flat(5f32));
+    let _modifier_multiply = hud_shield.add_modifier(StatModifier::PercentMultiply(2f32));
+    assert_eq!(hud_shield.value(), 20f32);
+    assert_eq!(party_shield.value(), 15f32);
+
+    shield.set(20f32);
+    assert_eq!(hud_shield.value(), 40f32);
+    assert_eq!(party_shield.value(), 25f32);
+
+    party_shield.set_base(1f32);
+    assert_eq!(shield.get(), 1f32);
+    assert_eq!(hud_shield.value(), 2f32);
+    assert_eq!(party_shield.value(), 6f32);
+    assert_eq!(hud_shield.stat_mut().base_value, 1f32);
+}
+
+#[test]
+fn checked_add_modifier() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    let (_modifier_zero, changed) = stat.checked_add_modifier(StatModifier::Flat(0f32));
+    assert!(!changed);
+    let (_modifier_flat, changed) = stat.checked_add_modifier(StatModifier::Flat(5f32));
+    assert!(changed);
+    assert_eq!(stat.value(), 15f32);
+
+    // hidden by the bounds
+    stat.set_bounds(None, Some(15f32));
+    let (_modifier_capped, changed) = stat.checked_add_modifier(StatModifier::Flat(5f32));
+    assert!(!changed);
+}
+
+#[test]
+fn order_span() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    assert_eq!(stat.order_span(), None);
+    let _modifier_1 = stat.add_modifier_with_order(StatModifier::Flat(1f32), 5);
+    assert_eq!(stat.order_span(), Some((5, 5)));
+    let _modifier_2 = stat.add_modifier_with_order(StatModifier::Flat(1f32), -3);
+    let _modifier_3 = stat.add_modifier_with_order(StatModifier::Flat(1f32), 2);
+    let dropped = stat.add_modifier_with_order(StatModifier::Flat(1f32), 100);
+    remove_modifier(&mut stat, dropped);
+    assert_eq!(stat.order_span(), Some((-3, 5)));
+}
+
+#[test]
+fn replace_modifier() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let modifier_aura = stat.add_modifier_with_order(StatModifier::Flat(1f32), 3);
+    let _modifier_multiply = stat.add_modifier_with_order(StatModifier::PercentMultiply(2f32), 1);
+    assert_eq!(stat.value(), 21f32);
+
+    assert!(stat.replace_modifier(&modifier_aura, StatModifier::Flat(5f32)));
+    assert_eq!(stat.value(), 25f32);
+    assert_eq!(stat.effective_order(&modifier_aura), Some(3));
+    assert_eq!(stat.len(), 2);
+
+    let other_handle = Stat::<1>::new(0f32).add_modifier(StatModifier::Flat(1f32));
+    assert!(!stat.replace_modifier(&other_handle, StatModifier::Flat(100f32)));
+
+    // the handle still removes the modifier
+    remove_modifier(&mut stat, modifier_aura);
+    assert_eq!(stat.value(), 20f32);
+}
+
+#[test]
+fn count_by_tag() {
+    const POISON: u64 = 1;
+    const BLESSING: u64 = 2;
+    let mut stat: Stat<4> = Stat::new(100f32);
+    let _modifier_poison_1 = stat.add_modifier_with_tag(StatModifier::Flat(-5f32), POISON);
+    let modifier_poison_2 = stat.add_modifier_with_tag(StatModifier::Flat(-5f32), POISON);
+    let _modifier_poison_3 = stat.add_modifier_with_tag(StatModifier::Flat(-5f32), POISON);
+    let _modifier_blessing = stat.add_modifier_with_tag(StatModifier::Flat(10f32), BLESSING);
+    let _modifier_untagged = stat.add_modifier(StatModifier::Flat(1f32));
+    assert_eq!(stat.count_by_tag(POISON), 3);
+    assert_eq!(stat.count_by_tag(BLESSING), 1);
+    assert_eq!(stat.count_by_tag(3), 0);
+    assert_eq!(stat.value(), 96f32);
+
+    remove_modifier(&mut stat, modifier_poison_2);
+    assert_eq!(stat.count_by_tag(POISON), 2);
+}
+
+#[test]
+fn value_as() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let modifier_big = stat.add_modifier(StatModifier::Flat(1000f32));
+    assert_eq!(stat.value_as::<u8>(), 255);
+    assert_eq!(stat.value_as::<u32>(), 1010);
+    remove_modifier(&mut stat, modifier_big);
+
+    let modifier_negative = stat.add_modifier(StatModifier::Flat(-20.6));
+    assert_eq!(stat.value_as::<u8>(), 0);
+    assert_eq!(stat.value_as::<i8>(), -11);
+    remove_modifier(&mut stat, modifier_negative);
+
+    let _modifier_half = stat.add_modifier(StatModifier::Flat(0.5));
+    assert_eq!(stat.value_as::<i16>(), 11);
+}
+
+#[test]
+fn modifier_is_no_op() {
+    assert!(StatModifier::Flat(0f32).is_no_op());
+    assert!(StatModifier::Flat(-0f32).is_no_op());
+    assert!(StatModifier::PercentAdd(0f32).is_no_op());
+    assert!(StatModifier::PercentMultiply(1f32).is_no_op());
+    assert!(StatModifier::Floor(f32::NEG_INFINITY).is_no_op());
+    assert!(StatModifier::Ceil(f32::INFINITY).is_no_op());
+    assert!(StatModifier::Diminishing(0f32).is_no_op());
+
+    assert!(!StatModifier::Flat(1f32).is_no_op());
+    assert!(!StatModifier::PercentAdd(0.1).is_no_op());
+    assert!(!StatModifier::PercentMultiply(0f32).is_no_op());
+    assert!(!StatModifier::Floor(0f32).is_no_op());
+    assert!(!StatModifier::Ceil(100f32).is_no_op());
+    assert!(!StatModifier::Diminishing(0.01).is_no_op());
+}
+
+#[test]
+fn exclusive_group() {
+    const SPEED_AURA: u16 = 0;
+    let mut stat: Stat<4> = Stat::new(10f32);
+    let modifier_medium = stat.add_modifier_with_group(StatModifier::PercentAdd(0.3), SPEED_AURA);
+    let modifier_strong = stat.add_modifier_with_group(StatModifier::PercentAdd(0.5), SPEED_AURA);
+    let _modifier_weak = stat.add_modifier_with_group(StatModifier::PercentAdd(0.1), SPEED_AURA);
+    let _modifier_other_group = stat.add_modifier_with_group(StatModifier::Flat(2f32), 1);
+    assert_eq!(stat.value(), 18f32);
+    assert_eq!(stat.len(), 4);
+    assert_eq!(stat.base_sensitivity(), 1.5);
+
+    remove_modifier(&mut stat, modifier_strong);
+    assert!(stat.approx_eq(15.6, 0.0001));
+    remove_modifier(&mut stat, modifier_medium);
+    assert!(stat.approx_eq(13.2, 0.0001));
+}
+
+#[test]
+fn exclusive_group_debuff() {
+    const SLOW: u16 = 0;
+    let mut stat: Stat<4> = Stat::new(10f32);
+    let _modifier_weak = stat.add_modifier_with_group(StatModifier::Flat(-2f32), SLOW);
+    let modifier_strong = stat.add_modifier_with_group(StatModifier::Flat(-5f32), SLOW);
+    assert_eq!(stat.value(), 5f32);
+    remove_modifier(&mut stat, modifier_strong);
+    assert_eq!(stat.value(), 8f32);
+
+    let mut stat: Stat<4> = Stat::new(10f32);
+    let _modifier_slow = stat.add_modifier_with_group(StatModifier::PercentMultiply(0.5), SLOW);
+    let _modifier_haste = stat.add_modifier_with_group(StatModifier::PercentMultiply(1.2), SLOW);
+    assert_eq!(stat.value(), 5f32);
+}
+
+#[test]
+fn tie_break() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    let _modifier_multiply = stat.add_modifier_with_order(StatModifier::PercentMultiply(2f32), 0);
+    let _modifier_flat = stat.add_modifier_with_order(StatModifier::Flat(5f32), 0);
+    let _modifier_later = stat.add_modifier_with_order(StatModifier::Flat(1f32), 1);
+    assert_eq!(stat.tie_break(), game_stat::TieBreak::ByInsertion);
+    // 10 * 2 + 5 + 1
+    assert_eq!(stat.value(), 26f32);
+
+    let hash = stat.config_hash();
+    stat.set_tie_break(game_stat::TieBreak::ByKind);
+    // (10 + 5) * 2 + 1
+    assert_eq!(stat.value(), 31f32);
+    assert_ne!(stat.config_hash(), hash);
+    assert_eq!(stat.recompute_from_scratch(), 31f32);
+    // the order they were added in is remembered
+    stat.set_tie_break(game_stat::TieBreak::ByInsertion);
+    assert_eq!(stat.value(), 26f32);
+    assert_eq!(stat.config_hash(), hash);
+
+    let stat = Stat::<2>::builder()
+        .base(10f32)
+        .tie_break(game_stat::TieBreak::ByKind)
+        .build();
+    assert_eq!(stat.tie_break(), game_stat::TieBreak::ByKind);
+}
+
+#[test]
+fn stat_sheet_entry() {
+    let mut sheet: StatSheet<&str, 2> = StatSheet::new();
+    let _modifier_flat = sheet
+        .entry("armor")
+        .or_insert(10f32)
+        .add_modifier(StatModifier::Flat(5f32));
+    assert_eq!(sheet.get("armor").unwrap().value(), 15f32);
+
+    // an existing stat is returned untouched
+    assert_eq!(sheet.entry("armor").or_insert(100f32).value(), 15f32);
+    assert_eq!(sheet.entry("speed").key(), &"speed");
+    assert_eq!(sheet.len(), 1);
+}
+
+#[test]
+fn value_with_base_matches_set_base() {
+    let build = || {
+        let mut stat: Stat<4> = Stat::new(10f32);
+        stat.set_bounds(Some(0f32), Some(500f32));
+        stat.set_max_multiplier(Some(3f32));
+        let handles = [
+            stat.add_modifier(StatModifier::Flat(5f32)),
+            stat.add_modifier(StatModifier::PercentAdd(0.5f32)),
+            stat.add_modifier_with_order(StatModifier::Ceil(200f32), 5),
+        ];
+        (stat, handles)
+    };
+    let (mut stat, _handles) = build();
+    let (mut other, _other_handles) = build();
+    for base in [-20f32, 0f32, 1f32, 10f32, 100f32, 400f32] {
+        *other.base_value_mut() = base;
+        assert_eq!(stat.value_with_base(base), other.value(), "base {base}");
+    }
+    assert_eq!(stat.value(), 22.5f32);
+
+    let _override = stat.push_override(1f32);
+    assert_eq!(stat.value_with_base(100f32), 1f32);
+}
+
+#[test]
+fn modifier_values() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(5f32));
+    let _modifier_percent = stat.add_modifier(StatModifier::PercentAdd(0.5f32));
+    let modifier_dropped = stat.add_modifier(StatModifier::Flat(100f32));
+    remove_modifier(&mut stat, modifier_dropped);
+    assert_eq!(stat.modifier_values().sum::<f32>(), 5.5f32);
+}
+
+#[test]
+fn set_modifier_order() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let modifier_flat = stat.add_modifier_with_order(StatModifier::Flat(5f32), 0);
+    let _modifier_multiply = stat.add_modifier_with_order(StatModifier::PercentMultiply(2f32), 10);
+    assert_eq!(stat.value(), 30f32);
+    assert!(stat.set_modifier_order(&modifier_flat, 20));
+    assert_eq!(stat.value(), 25f32);
+    assert_eq!(stat.order_span(), Some((10, 20)));
+
+    let mut other: Stat<2> = Stat::new(10f32);
+    assert!(!other.set_modifier_order(&modifier_flat, 0));
+    remove_modifier(&mut stat, modifier_flat);
+    assert_eq!(stat.value(), 20f32);
+}
+
+#[test]
+fn try_from_iter() {
+    let defs = (1..=3).map(|i| ModifierDef::from(StatModifier::Flat(i as f32)));
+    let (stat, handles) = Stat::<3>::try_from_iter(10f32, defs.clone()).unwrap();
+    assert_eq!(stat.value(), 16f32);
+    assert_eq!(handles.len(), 3);
+    assert!(stat.capacity_utilization() <= 1f32);
+
+    let mut consumed = 0;
+    let overflowing = defs.inspect(|_| consumed += 1);
+    let error = Stat::<2>::try_from_iter(10f32, overflowing).unwrap_err();
+    assert_eq!((error.needed, error.available), (3, 2));
+    assert_eq!(consumed, 3);
+}
+
+#[test]
+fn increased_and_more() {
+    let mut stat: Stat<8> = Stat::new(100f32);
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(20f32));
+    let _modifier_increased_1 = stat.add_modifier(StatModifier::increased(0.5));
+    let _modifier_more_1 = stat.add_modifier(StatModifier::more(0.2));
+    let _modifier_increased_2 = stat.add_modifier(StatModifier::increased(0.3));
+    let _modifier_more_2 = stat.add_modifier(StatModifier::more(0.1));
+    // (100 + 20) * (1 + 0.5 + 0.3) * 1.2 * 1.1
+    assert!(stat.approx_eq(285.12, 0.001));
+    // increased modifiers on their own would stack multiplicatively: 120 * 1.5 * 1.3 * 1.2 * 1.1
+    assert!(!stat.approx_eq(308.88, 1f32));
+
+    assert_eq!(StatModifier::increased(0.5).kind(), ModifierKind::Increased);
+    assert_eq!(ModifierKind::try_from(7), Ok(ModifierKind::More));
+    assert!(stat.base_sensitivity() > 0f32);
+}
+
+#[test]
+fn increased_in_separate_orders() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    let _modifier_increased_1 = stat.add_modifier_with_order(StatModifier::Increased(1f32), 0);
+    let _modifier_increased_2 = stat.add_modifier_with_order(StatModifier::Increased(1f32), 0);
+    let _modifier_increased_3 = stat.add_modifier_with_order(StatModifier::Increased(1f32), 5);
+    // 10 * 3 * 2
+    assert_eq!(stat.value(), 60f32);
+    assert_eq!(stat.value_with_base(1f32), 6f32);
+}
+
+#[cfg(not(feature = "manual-pruning"))]
+#[test]
+fn dead_modifiers() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    let modifier_flat = stat.add_modifier(StatModifier::Flat(1f32));
+    let modifier_multiply = stat.add_modifier_with_order(StatModifier::PercentMultiply(2f32), 5);
+    let _modifier_kept = stat.add_modifier(StatModifier::PercentAdd(0.5));
+    assert_eq!(stat.dead_modifiers().count(), 0);
+
+    drop(modifier_flat);
+    drop(modifier_multiply);
+    let dead: Vec<(ModifierKind, i32)> = stat.dead_modifiers().collect();
+    assert_eq!(
+        dead,
+        vec![(ModifierKind::Flat, 0), (ModifierKind::PercentMultiply, 5)]
+    );
+    // looking doesn't clean up
+    assert_eq!(stat.raw_slot_count(), 3);
+
+    assert_eq!(stat.prune(), 2);
+    assert_eq!(stat.dead_modifiers().count(), 0);
+}
+
+#[test]
+fn clone_config_onto() {
+    let mut template: Stat<4> = Stat::new(0f32);
+    let modifier_flat = template.add_modifier(StatModifier::Flat(5f32));
+    let _modifier_multiply =
+        template.add_modifier_with_order(StatModifier::PercentMultiply(2f32), 7);
+    template.add_permanent_modifier(StatModifier::Flat(1f32));
+    let dropped = template.add_modifier(StatModifier::Flat(100f32));
+    remove_modifier(&mut template, dropped);
+
+    let mut target: Stat<8> = Stat::new(10f32);
+    target.set_bounds(None, Some(30f32));
+    let _modifier_own = target.add_modifier(StatModifier::Flat(2f32));
+    let handles = template.clone_config_onto(&mut target).unwrap();
+    assert_eq!(handles.len(), 3);
+    assert_eq!(target.base_value, 10f32);
+    assert_eq!(target.len(), 4);
+    // (10 + 2 + 5 + 1) * 2, bounded
+    assert_eq!(target.value(), 30f32);
+    target.set_bounds(None, None);
+    assert_eq!(target.value(), 36f32);
+    assert_eq!(target.effective_order(&handles[2]), Some(7));
+
+    // the copies are independent of the template handles
+    remove_modifier(&mut template, modifier_flat);
+    assert_eq!(target.value(), 36f32);
+    let [modifier_flat_copy, modifier_permanent_copy, modifier_multiply_copy] =
+        <[StatModifierHandle; 3]>::try_from(handles).unwrap();
+    remove_modifier(&mut target, modifier_flat_copy);
+    remove_modifier(&mut target, modifier_multiply_copy);
+    // a permanent modifier doesn't go away with its handle
+    drop(modifier_permanent_copy);
+    // only the permanent copy and the target's own modifier are left
+    assert_eq!(target.value(), 13f32);
+
+    // too many modifiers, nothing is added
+    let mut small: Stat<2> = Stat::new(10f32);
+    let _modifier_small = small.add_modifier(StatModifier::Flat(1f32));
+    let error = template.clone_config_onto(&mut small).unwrap_err();
+    assert_eq!((error.needed, error.available), (2, 1));
+    assert_eq!(small.len(), 1);
+    assert_eq!(small.value(), 11f32);
+}
+
+#[test]
+fn conditional_modifiers() {
+    use game_stat::Condition;
+    let mut stat: Stat<4> = Stat::new(10f32);
+    let _modifier_berserk =
+        stat.add_conditional_modifier(StatModifier::PercentAdd(0.5), Condition::Below(0.5));
+    let _modifier_last_stand =
+        stat.add_conditional_modifier(StatModifier::Flat(5f32), Condition::Below(0.1));
+    let _modifier_healthy =
+        stat.add_conditional_modifier(StatModifier::Flat(1f32), Condition::AtLeast(1f32));
+    // no condition value, none apply
+    assert_eq!(stat.value(), 10f32);
+
+    assert_eq!(stat.value_at_condition(1f32), 11f32);
+    assert_eq!(stat.value_at_condition(0.5), 10f32);
+    assert_eq!(stat.value_at_condition(0.3), 15f32);
+    assert_eq!(stat.value_at_condition(0.05), 22.5);
+    // previews don't change the stat
+    assert_eq!(stat.value(), 10f32);
+
+    stat.set_condition_value(Some(0.3));
+    assert_eq!(stat.condition_value(), Some(0.3));
+    assert_eq!(stat.value(), 15f32);
+    assert_eq!(stat.value_at_condition(1f32), 11f32);
+}
+
+#[cfg(not(feature = "manual-pruning"))]
+#[test]
+fn scheduler_long_session() {
+    let mut scheduler = game_stat::ModifierScheduler::new();
+    // about 55 hours of play
+    for _ in 0..200 {
+        scheduler.advance(1000f32);
+    }
+    let mut stat: Stat<2> = Stat::new(10f32);
+    scheduler.schedule(stat.add_modifier(StatModifier::Flat(5f32)), 1f32);
+    for _ in 0..59 {
+        scheduler.advance(1f32 / 60f32);
+    }
+    assert_eq!(stat.value(), 15f32);
+    scheduler.advance(1f32 / 60f32);
+    assert_eq!(stat.value(), 10f32);
+}
+
+#[cfg(not(feature = "manual-pruning"))]
+#[test]
+fn scheduler_expires_across_stats() {
+    let mut scheduler = game_stat::ModifierScheduler::new();
+    let mut attack: Stat<4> = Stat::new(10f32);
+    let mut defense: Stat<4> = Stat::new(10f32);
+    scheduler.schedule(attack.add_modifier(StatModifier::Flat(1f32)), 3f32);
+    scheduler.schedule(defense.add_modifier(StatModifier::Flat(2f32)), 1f32);
+    scheduler.schedule(attack.add_modifier(StatModifier::Flat(4f32)), 2f32);
+    let _modifier_kept = defense.add_modifier(StatModifier::Flat(8f32));
+    assert_eq!(scheduler.len(), 3);
+    assert_eq!(scheduler.time_until_next(), Some(1f32));
+    assert_eq!(attack.value(), 15f32);
+    assert_eq!(defense.value(), 20f32);
+
+    assert_eq!(scheduler.advance(0.5), 0);
+    assert_eq!(scheduler.advance(0.5), 1);
+    assert_eq!(attack.value(), 15f32);
+    assert_eq!(defense.value(), 18f32);
+
+    // scheduled after time advanced, expires relative to now
+    scheduler.schedule(defense.add_modifier(StatModifier::Flat(16f32)), 0.5);
+    assert_eq!(scheduler.advance(1f32), 2);
+    assert_eq!(attack.value(), 11f32);
+    assert_eq!(defense.value(), 18f32);
+
+    assert_eq!(scheduler.advance(10f32), 1);
+    assert!(scheduler.is_empty());
+    assert_eq!(scheduler.time_until_next(), None);
+    assert_eq!(attack.value(), 10f32);
+    assert_eq!(defense.value(), 18f32);
+}
+
+#[test]
+fn override_stack() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(5f32));
+    let override_1 = stat.push_override(3f32);
+    let override_2 = stat.push_override(1f32);
+    assert_eq!(stat.value(), 1f32);
+
+    drop(override_2);
+    assert_eq!(stat.value(), 3f32);
+
+    // pushed overrides beat the one that was set
+    stat.set_override(Some(100f32));
+    assert_eq!(stat.value(), 3f32);
+    drop(override_1);
+    assert_eq!(stat.value(), 100f32);
+    stat.set_override(None);
+    assert_eq!(stat.value(), 15f32);
+
+    // dropping one in the middle doesn't change the top
+    let _override_3 = stat.push_override(7f32);
+    let override_4 = stat.push_override(8f32);
+    let override_5 = stat.push_override(9f32);
+    drop(override_4);
+    assert_eq!(stat.value(), 9f32);
+    drop(override_5);
+    assert_eq!(stat.value(), 7f32);
+}
+
+#[test]
+fn permanent_modifier_operators() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    stat += 5f32;
+    assert_eq!(stat.value(), 15f32);
+    stat *= 2f32;
+    assert_eq!(stat.value(), 30f32);
+    assert_eq!(stat.base_value, 10f32);
+    assert_eq!(stat.len(), 2);
+
+    let modifier_temporary = stat.add_modifier(StatModifier::Flat(5f32));
+    assert_eq!(stat.value(), 40f32);
+    remove_modifier(&mut stat, modifier_temporary);
+    assert_eq!(stat.value(), 30f32);
+
+    stat.add_permanent_modifier_with_order(StatModifier::Flat(-10f32), 10);
+    assert_eq!(stat.value(), 20f32);
+    assert_eq!(
+        stat.remove_by_source(game_stat::ModifierSource::Unspecified),
+        3
+    );
+    assert_eq!(stat.value(), 10f32);
+}
+
+#[test]
+fn named_modifiers() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    let _modifier_rage = stat.add_modifier_with_name(StatModifier::PercentMultiply(2f32), "Rage");
+    let _modifier_sword = stat.add_modifier_with_name(StatModifier::Flat(5f32), "Sword of Fire");
+    let _modifier_unnamed = stat.add_modifier(StatModifier::Flat(1f32));
+
+    let infos: Vec<game_stat::ModifierInfo> = stat.modifiers().collect();
+    assert_eq!(infos.len(), 3);
+    assert_eq!(infos[0].name, Some("Sword of Fire"));
+    assert_eq!(infos[1].name, None);
+    assert_eq!(infos[2].name, Some("Rage"));
+    assert_eq!(infos[2].order, 2);
+    assert!(matches!(
+        infos[2].modifier,
+        StatModifier::PercentMultiply(_)
+    ));
+    assert_eq!(
+        stat.describe(),
+        "base: 10\n\
+         Flat(5.0) order: 0 name: Sword of Fire\n\
+         Flat(1.0) order: 0\n\
+         PercentMultiply(2.0) order: 2 name: Rage\n\
+         value: 32\n"
+    );
+}
+
+#[test]
+fn capacity_utilization() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    assert_eq!(stat.capacity_utilization(), 0f32);
+    let _modifier_1 = stat.add_modifier(StatModifier::Flat(1f32));
+    let _modifier_2 = stat.add_modifier(StatModifier::Flat(1f32));
+    assert_eq!(stat.capacity_utilization(), 0.5);
+    let _modifier_3 = stat.add_modifier(StatModifier::Flat(1f32));
+    let _modifier_4 = stat.add_modifier(StatModifier::Flat(1f32));
+    assert_eq!(stat.capacity_utilization(), 1f32);
+    let _modifier_5 = stat.add_modifier(StatModifier::Flat(1f32));
+    assert_eq!(stat.capacity_utilization(), 1.25);
+
+    let stat: Stat<0> = Stat::new(10f32);
+    assert_eq!(stat.capacity_utilization(), 1f32);
+}
+
+#[test]
+fn delayed_modifier() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let _modifier_delayed =
+        stat.add_modifier_with_delay(StatModifier::PercentAdd(0.5), 2f32, Some(3f32));
+    // present, but waiting
+    assert_eq!(stat.len(), 1);
+    assert_eq!(stat.value(), 10f32);
+    assert!(stat.tick(1.5).is_empty());
+    assert_eq!(stat.value(), 10f32);
+
+    // the extra 0.5 counts towards the duration
+    assert!(stat.tick(1f32).is_empty());
+    assert_eq!(stat.value(), 15f32);
+    assert!(stat.tick(2f32).is_empty());
+    assert_eq!(stat.value(), 15f32);
+
+    let expired = stat.tick(0.5);
+    assert_eq!(expired.len(), 1);
+    assert_eq!(stat.value(), 10f32);
+    assert!(stat.is_empty());
+}
+
+#[test]
+fn delayed_modifier_outlives_expiry_during_delay() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let _modifier_delayed =
+        stat.add_modifier_with_delay(StatModifier::PercentAdd(0.5), 2f32, Some(1f32));
+    let _modifier_short = stat.add_modifier_with_duration(StatModifier::Flat(2f32), 1f32);
+    assert_eq!(stat.value(), 12f32);
+
+    // the short one expires while the delayed one is still waiting
+    let expired = stat.tick(1f32);
+    assert_eq!(expired.len(), 1);
+    assert!(matches!(expired[0].modifier, StatModifier::Flat(v) if v == 2f32));
+    assert_eq!(stat.len(), 1);
+    assert_eq!(stat.value(), 10f32);
+
+    // active
+    assert!(stat.tick(1f32).is_empty());
+    assert_eq!(stat.value(), 15f32);
+
+    // expired
+    let expired = stat.tick(1f32);
+    assert_eq!(expired.len(), 1);
+    assert!(matches!(expired[0].modifier, StatModifier::PercentAdd(v) if v == 0.5));
+    assert_eq!(stat.value(), 10f32);
+    assert!(stat.is_empty());
+}
+
+#[test]
+fn base_value_mut() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(5f32));
+    assert_eq!(stat.value(), 15f32);
+    *stat.base_value_mut() += 5f32;
+    assert_eq!(stat.base_value, 15f32);
+    assert_eq!(stat.value(), 20f32);
+    {
+        let mut base_value = stat.base_value_mut();
+        *base_value *= 2f32;
+        *base_value -= 10f32;
+    }
+    assert_eq!(stat.value(), 25f32);
+}
+
+#[test]
+fn remove_by_source() {
+    use game_stat::ModifierSource;
+    let mut stat: Stat<4> = Stat::new(100f32);
+    let _modifier_armor =
+        stat.add_modifier_with_source(StatModifier::Flat(20f32), ModifierSource::Equipment);
+    let modifier_potion =
+        stat.add_modifier_with_source(StatModifier::Flat(50f32), ModifierSource::Consumable);
+    let _modifier_elixir = stat.add_modifier_with_source(
+        StatModifier::PercentMultiply(2f32),
+        ModifierSource::Consumable,
+    );
+    let _modifier_plain = stat.add_modifier(StatModifier::Flat(1f32));
+    assert_eq!(stat.value(), 342f32);
+
+    assert_eq!(stat.remove_by_source(ModifierSource::Consumable), 2);
+    assert_eq!(stat.value(), 121f32);
+    assert_eq!(stat.len(), 2);
+    assert_eq!(stat.effective_order(&modifier_potion), None);
+    assert_eq!(stat.remove_by_source(ModifierSource::Consumable), 0);
+
+    assert_eq!(stat.remove_by_source(ModifierSource::Unspecified), 1);
+    assert_eq!(stat.value(), 120f32);
+}
+
+#[cfg(not(feature = "manual-pruning"))]
+#[test]
+fn stat_like_trait_objects() {
+    use game_stat::StatLike;
+    let mut stats: Vec<Box<dyn StatLike>> = vec![
+        Box::new(Stat::<3>::new(10f32)),
+        Box::new(Stat::<5>::new(20f32)),
+    ];
+    let handles: Vec<StatModifierHandle> = stats
+        .iter_mut()
+        .map(|stat| stat.add_modifier(StatModifier::Flat(5f32)))
+        .collect();
+    assert_eq!(stats[0].value(), 15f32);
+    assert_eq!(stats[1].value(), 25f32);
+    assert_eq!(stats[1].base_value(), 20f32);
+    assert_eq!(stats[0].capacity(), 3);
+    assert_eq!(stats[1].capacity(), 5);
+    assert_eq!(stats[0].len(), 1);
+
+    drop(handles);
+    assert!(stats.iter().all(|stat| stat.is_empty()));
+    assert_eq!(stats[1].value(), 20f32);
+}
+
+#[test]
+fn buffed_debuffed() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    assert!(!stat.is_buffed());
+    assert!(!stat.is_debuffed());
+    assert_eq!(stat.net_effect(), 0f32);
+
+    let modifier_buff = stat.add_modifier(StatModifier::PercentMultiply(1.5));
+    assert!(stat.is_buffed());
+    assert!(!stat.is_debuffed());
+    assert_eq!(stat.net_effect(), 5f32);
+
+    // cancel each other out
+    let _modifier_debuff = stat.add_modifier(StatModifier::Flat(-10f32 / 3f32));
+    assert!(!stat.is_buffed());
+    assert!(!stat.is_debuffed());
+
+    remove_modifier(&mut stat, modifier_buff);
+    assert!(stat.is_debuffed());
+    assert!(stat.net_effect() < 0f32);
+}
+
+#[test]
+fn split_compute() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    stat.set_bounds(None, Some(100f32));
+    let _modifier_flat = stat.add_modifier_with_order(StatModifier::Flat(5f32), 0);
+    let _modifier_percent = stat.add_modifier_with_order(StatModifier::PercentAdd(0.5), 1);
+    let _modifier_armor = stat.add_modifier_with_order(StatModifier::Flat(-2.5), 5);
+
+    let partial = stat.split_compute(5);
+    assert_eq!(partial.value(), 22.5);
+    // crit between the phases
+    assert_eq!(partial.finish(partial.value() * 2f32), 42.5);
+    // nothing injected gives the normal value
+    assert_eq!(partial.finish(partial.value()), stat.value());
+    // bounds still apply at the end
+    assert_eq!(partial.finish(1000f32), 100f32);
+
+    let partial = stat.split_compute(i32::MIN);
+    assert_eq!(partial.value(), 10f32);
+    assert_eq!(partial.finish(partial.value()), 20f32);
+}
+
+#[cfg(not(feature = "manual-pruning"))]
+#[test]
+fn cached_value() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let modifier = stat.add_modifier(StatModifier::Flat(5f32));
+    assert_eq!(stat.cached_value(), 15f32);
+
+    drop(modifier);
+    // the dropped modifier is only noticed on the next read
+    assert_eq!(stat.cached_value(), 15f32);
+    assert_eq!(stat.len(), 0);
+    assert_eq!(stat.value(), 10f32);
+    assert_eq!(stat.cached_value(), 10f32);
+}
+
+#[test]
+fn compute_trace() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    assert!(stat.compute_trace().is_empty());
+
+    let _modifier_percent = stat.add_modifier(StatModifier::PercentAdd(0.5));
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(5f32));
+    let trace = stat.compute_trace();
+    assert_eq!(trace.len(), 2);
+    assert_eq!(trace[0].order, 0);
+    assert_eq!(trace[0].kind, ModifierKind::Flat);
+    assert_eq!(
+        (trace[0].value_before, trace[0].value_after),
+        (10f32, 15f32)
+    );
+    assert_eq!(trace[1].order, 1);
+    assert_eq!(trace[1].kind, ModifierKind::PercentAdd);
+    assert_eq!((trace[1].value_before, trace[1].value_after), (15f32, 22.5));
+    assert_eq!(trace[1].value_after, stat.value());
+
+    // increased modifiers of one order are a single step
+    let _modifier_increased_1 = stat.add_modifier_with_order(StatModifier::Increased(0.5), 5);
+    let _modifier_increased_2 = stat.add_modifier_with_order(StatModifier::Increased(0.5), 5);
+    let trace = stat.compute_trace();
+    assert_eq!(trace.len(), 3);
+    assert_eq!(trace[2].kind, ModifierKind::Increased);
+    assert_eq!((trace[2].value_before, trace[2].value_after), (22.5, 45f32));
+}
+
+#[test]
+fn merge_into() {
+    let mut equipment: Stat<2> = Stat::new(0f32);
+    let _modifier_flat = equipment.add_modifier(StatModifier::Flat(5f32));
+    let _modifier_percent = equipment.add_modifier(StatModifier::PercentAdd(0.5));
+
+    let mut stat: Stat<6> = Stat::new(10f32);
+    let _modifier_base = stat.add_modifier(StatModifier::Flat(5f32));
+    let handles = equipment.merge_into(&mut stat).unwrap();
+    assert_eq!(handles.len(), 2);
+    // (10 + 5 + 5) * 1.5
+    assert_eq!(stat.value(), 30f32);
+    for handle in handles {
+        remove_modifier(&mut stat, handle);
+    }
+    assert_eq!(stat.value(), 15f32);
+
+    // too many modifiers, nothing is added
+    let mut full: Stat<2> = Stat::new(10f32);
+    let _modifier_full = full.add_modifier(StatModifier::Flat(1f32));
+    let mut equipment: Stat<2> = Stat::new(0f32);
+    let _modifier_1 = equipment.add_modifier(StatModifier::Flat(5f32));
+    let _modifier_2 = equipment.add_modifier(StatModifier::Flat(5f32));
+    let equipment = equipment.merge_into(&mut full).unwrap_err();
+    assert_eq!(equipment.len(), 2);
+    assert_eq!(full.len(), 1);
+    assert_eq!(full.value(), 11f32);
+}
+
+#[cfg(not(feature = "manual-pruning"))]
+#[test]
+fn can_fit() {
+    let mut stat: Stat<3> = Stat::new(10f32);
+    assert!(stat.can_fit(3));
+    assert!(!stat.can_fit(4));
+
+    let _modifier_kept = stat.add_modifier(StatModifier::Flat(1f32));
+    let modifier_dropped_1 = stat.add_modifier(StatModifier::Flat(1f32));
+    let modifier_dropped_2 = stat.add_modifier(StatModifier::Flat(1f32));
+    assert!(stat.can_fit(0));
+    assert!(!stat.can_fit(1));
+
+    drop(modifier_dropped_1);
+    drop(modifier_dropped_2);
+    // every slot is still taken, but the dead ones make room
+    assert_eq!(stat.raw_slot_count(), 3);
+    assert!(stat.can_fit(2));
+    assert!(!stat.can_fit(3));
+    assert!(!stat.can_fit(usize::MAX));
+}
+
+#[test]
+fn modifier_eq_hash() {
+    use std::collections::HashSet;
+
+    let modifiers = [
+        StatModifier::Flat(5f32),
+        StatModifier::Flat(5f32),
+        StatModifier::PercentAdd(5f32),
+        StatModifier::Flat(5f32),
+        StatModifier::Flat(f32::NAN),
+        StatModifier::Flat(f32::NAN),
+    ];
+    let unique: HashSet<StatModifier> = modifiers.into_iter().collect();
+    assert_eq!(unique.len(), 3);
+    assert_eq!(StatModifier::Flat(f32::NAN), StatModifier::Flat(f32::NAN));
+    assert_ne!(StatModifier::Flat(0f32), StatModifier::Flat(-0f32));
+
+    let defs = [
+        ModifierDef::new(StatModifier::Flat(5f32), 0),
+        ModifierDef::new(StatModifier::Flat(5f32), 1),
+        ModifierDef::from(StatModifier::Flat(5f32)),
+    ];
+    let unique: HashSet<ModifierDef> = defs.into_iter().collect();
+    assert_eq!(unique.len(), 2);
+}
+
+#[test]
+fn staged_modifier() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(5f32));
+
+    let staged = stat.stage_modifier(StatModifier::PercentMultiply(2f32));
+    assert_eq!(stat.value(), 15f32);
+    assert_eq!(stat.preview_staged(), 30f32);
+    assert_eq!(stat.len(), 2);
+    // across frames nothing changes until it's committed
+    stat.tick(1f32);
+    assert_eq!(stat.value(), 15f32);
+    let modifier_multiply = stat.commit_staged(staged).unwrap();
+    assert_eq!(stat.value(), 30f32);
+    assert_eq!(stat.preview_staged(), 30f32);
+    remove_modifier(&mut stat, modifier_multiply);
+    assert_eq!(stat.value(), 15f32);
+
+    let staged = stat.stage_modifier(StatModifier::PercentMultiply(2f32));
+    assert_eq!(stat.preview_staged(), 30f32);
+    assert!(stat.rollback_staged(staged));
+    assert_eq!(stat.value(), 15f32);
+    assert_eq!(stat.preview_staged(), 15f32);
+    assert_eq!(stat.len(), 1);
+
+    // staged on another stat
+    let mut other: Stat<2> = Stat::new(10f32);
+    let staged = other.stage_modifier(StatModifier::Flat(1f32));
+    assert!(stat.commit_staged(staged).is_none());
+}
+
+#[test]
+fn base_plus_flats() {
+    let mut stat: Stat<6> = Stat::new(10f32);
+    assert_eq!(stat.base_plus_flats(), 10f32);
+
+    let _modifier_flat_1 = stat.add_modifier(StatModifier::Flat(5f32));
+    let _modifier_percent = stat.add_modifier(StatModifier::PercentAdd(0.5));
+    // flats after the percents are still counted
+    let _modifier_flat_2 = stat.add_modifier_with_order(StatModifier::Flat(-2f32), 10);
+    let _modifier_multiply = stat.add_modifier(StatModifier::PercentMultiply(3f32));
+    let _modifier_staged = stat.stage_modifier(StatModifier::Flat(100f32));
+    assert_eq!(stat.base_plus_flats(), 13f32);
+    assert_eq!(stat.value(), 65.5);
+}
+
+#[test]
+fn value_with_spillover() {
+    let mut stat: Stat<2> = Stat::new(90f32);
+    let modifier = stat.add_modifier(StatModifier::Flat(30f32));
+    // no bounds, nothing spills over
+    assert_eq!(stat.value_with_spillover(), (120f32, 0f32));
+
+    stat.set_bounds(Some(50f32), Some(100f32));
+    assert_eq!(stat.value_with_spillover(), (100f32, 20f32));
+    remove_modifier(&mut stat, modifier);
+    assert_eq!(stat.value_with_spillover(), (90f32, 0f32));
+
+    let _modifier_debuff = stat.add_modifier(StatModifier::Flat(-60f32));
+    assert_eq!(stat.value_with_spillover(), (50f32, -20f32));
+
+    // rounding and multiplier limits aren't bounds, nothing spills over
+    let mut stat: Stat<2> = Stat::new(10f32);
+    stat.set_rounding(Some(game_stat::Rounding::Floor));
+    stat.set_max_multiplier(Some(2f32));
+    let _modifier_percent = stat.add_modifier(StatModifier::PercentAdd(0.07));
+    assert_eq!(stat.value_with_spillover(), (10f32, 0f32));
+    let _modifier_multiply = stat.add_modifier(StatModifier::PercentMultiply(5f32));
+    assert_eq!(stat.value_with_spillover(), (20f32, 0f32));
+    assert_eq!(stat.raw_value(), 20f32);
+    stat.set_bounds(None, Some(15f32));
+    assert_eq!(stat.value_with_spillover(), (15f32, 5f32));
+}
+
+#[cfg(not(feature = "manual-pruning"))]
+#[test]
+fn is_empty() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    assert!(stat.is_empty());
+
+    let modifier_1 = stat.add_modifier(StatModifier::Flat(5f32));
+    let modifier_2 = stat.add_modifier(StatModifier::Flat(5f32));
+    assert!(!stat.is_empty());
+    drop(modifier_1);
+    assert!(!stat.is_empty());
+    drop(modifier_2);
+    // not cleaned up yet, but nothing applies
+    assert_eq!(stat.raw_slot_count(), 2);
+    assert!(stat.is_empty());
+    assert_eq!(stat.value(), 10f32);
+}
+
+#[test]
+fn value_with_auras() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let _modifier_flat = stat.add_modifier_with_order(StatModifier::Flat(5f32), 0);
+    let _modifier_late = stat.add_modifier_with_order(StatModifier::Flat(1f32), 10);
+
+    let mut aura_1: Stat<2> = Stat::new(100f32);
+    let modifier_aura_1 = aura_1.add_modifier_with_order(StatModifier::PercentMultiply(2f32), 5);
+    let mut aura_2: Stat<4> = Stat::new(100f32);
+    let _modifier_aura_2 = aura_2.add_modifier_with_order(StatModifier::Flat(3f32), 1);
+
+    assert_eq!(stat.value_with_auras::<4>(&[]), 16f32);
+    // (10 + 5) * 2 + 1
+    assert_eq!(stat.value_with_auras(&[&aura_1]), 31f32);
+    // interleaved by order: (10 + 5 + 3) * 2 + 1
+    let aura_2: Stat<2> = aura_2.into_capacity().unwrap();
+    assert_eq!(stat.value_with_auras(&[&aura_1, &aura_2]), 37f32);
+    assert_eq!(stat.value(), 16f32);
+
+    remove_modifier(&mut aura_1, modifier_aura_1);
+    assert_eq!(stat.value_with_auras(&[&aura_1, &aura_2]), 19f32);
+}
+
+#[test]
+fn bake() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    let modifier_flat = stat.add_modifier(StatModifier::Flat(5f32));
+    let _modifier_percent = stat.add_modifier(StatModifier::PercentAdd(1f32));
+    stat.add_permanent_modifier(StatModifier::Flat(5f32));
+    assert_eq!(stat.value(), 40f32);
+
+    stat.bake();
+    assert_eq!(stat.base_value, 40f32);
+    assert_eq!(stat.value(), 40f32);
+    assert!(stat.is_empty());
+    assert_eq!(stat.raw_slot_count(), 0);
+    // the old handles don't control anything anymore
+    assert!(!stat.replace_modifier(&modifier_flat, StatModifier::Flat(1f32)));
+    drop(modifier_flat);
+    assert_eq!(stat.value(), 40f32);
+
+    let bounded = || {
+        let mut stat: Stat<2> = Stat::new(10f32);
+        stat.set_bounds(None, Some(15f32));
+        stat.add_permanent_modifier(StatModifier::Flat(10f32));
+        stat
+    };
+    let mut stat = bounded();
+    stat.bake();
+    assert_eq!(stat.base_value, 15f32);
+    let mut stat = bounded();
+    stat.bake_raw();
+    assert_eq!(stat.base_value, 20f32);
+    assert_eq!(stat.value(), 15f32);
+
+    // the override isn't baked
+    let mut stat = bounded();
+    stat.set_override(Some(999f32));
+    stat.bake();
+    assert_eq!(stat.base_value, 15f32);
+    assert_eq!(stat.value(), 999f32);
+    stat.set_override(None);
+    assert_eq!(stat.value(), 15f32);
+    let mut stat = bounded();
+    let _frozen = stat.push_override(0f32);
+    stat.bake_raw();
+    assert_eq!(stat.base_value, 20f32);
+}
+
+#[test]
+fn modifier_orders() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    assert_eq!(stat.modifier_orders().count(), 0);
+
+    let _modifier_late = stat.add_modifier_with_order(StatModifier::Flat(1f32), 20);
+    let _modifier_percent = stat.add_modifier(StatModifier::PercentAdd(0.5));
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(5f32));
+    let modifier_early = stat.add_modifier_with_order(StatModifier::Flat(5f32), -5);
+    assert_eq!(
+        stat.modifier_orders().collect::<Vec<_>>(),
+        vec![-5, 0, 1, 20]
+    );
+
+    remove_modifier(&mut stat, modifier_early);
+    let _modifier_flat_2 = stat.add_modifier(StatModifier::Flat(5f32));
+    assert_eq!(stat.modifier_orders().collect::<Vec<_>>(), vec![0, 1, 20]);
+}
+
+#[test]
+fn recalculate_if_dirty() {
+    let mut sheet: StatSheet<&str, 2> = StatSheet::new();
+    sheet.insert("attack", Stat::new(10f32));
+    let stat = sheet.get_mut("attack").unwrap();
+    assert!(!stat.recalculate_if_dirty());
+
+    // adding through a sheet defers the calculation
+    let _modifier = sheet
+        .add_modifier("attack", StatModifier::Flat(5f32))
+        .unwrap();
+    let stat = sheet.get_mut("attack").unwrap();
+    assert_eq!(stat.cached_value(), 10f32);
+    assert!(stat.recalculate_if_dirty());
+    assert_eq!(stat.cached_value(), 15f32);
+    assert!(!stat.recalculate_if_dirty());
+
+    *stat.base_value_mut() = 20f32;
+    assert!(stat.recalculate_if_dirty());
+    assert!(!stat.recalculate_if_dirty());
+    assert_eq!(stat.cached_value(), 25f32);
+}
+
+#[test]
+fn packed_modifiers() {
+    use game_stat::PackedModifier;
+
+    assert_eq!(std::mem::size_of::<PackedModifier>(), 8);
+    let mut stat: Stat<4> = Stat::new(10f32);
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(5f32));
+    let _modifier_percent = stat.add_modifier(StatModifier::PercentAdd(0.5));
+    let _modifier_more = stat.add_modifier(StatModifier::More(-0.25));
+
+    let bytes: Vec<[u8; 8]> = stat
+        .pack_modifiers()
+        .iter()
+        .map(PackedModifier::to_bytes)
+        .collect();
+    assert_eq!(bytes[0][0], ModifierKind::Flat as u8);
+    let packed: Vec<PackedModifier> = bytes.into_iter().map(PackedModifier::from_bytes).collect();
+    assert_eq!(packed, stat.pack_modifiers());
+
+    let mut replicated: Stat<4> = Stat::new(10f32);
+    let handles = replicated.apply_packed(&packed).unwrap();
+    assert_eq!(handles.len(), 3);
+    assert_eq!(replicated.value(), stat.value());
+    assert_eq!(replicated.pack_modifiers(), packed);
+
+    // unknown kind, nothing is added
+    let mut bytes = PackedModifier::from(StatModifier::Flat(1f32)).to_bytes();
+    bytes[0] = 200;
+    let invalid = [packed[0], PackedModifier::from_bytes(bytes)];
+    assert!(replicated.apply_packed(&invalid).is_none());
+    assert_eq!(replicated.len(), 3);
+    assert_eq!(StatModifier::try_from(invalid[1]).unwrap_err(), 200);
+}
+
+#[test]
+fn f64_accumulation() {
+    // buffs and debuffs, keeping the value around 1.0
+    let percents: Vec<f32> = (0..48)
+        .map(|i| 0.0123 * (1 + i % 5) as f32 * if i % 2 == 0 { 1f32 } else { -1f32 })
+        .collect();
+    let reference = percents
+        .iter()
+        .fold(1.1f64, |value, percent| value * (1.0 + *percent as f64));
+
+    let mut stat: Stat<48> = Stat::new(1.1);
+    let _handles: Vec<_> = percents
+        .iter()
+        .enumerate()
+        .map(|(order, percent)| {
+            stat.add_modifier_with_order(StatModifier::PercentAdd(*percent), order as i32)
+        })
+        .collect();
+    let f32_error = (stat.value() as f64 - reference).abs();
+    stat.set_f64_accumulation(true);
+    assert!(stat.f64_accumulation());
+    let f64_error = (stat.value() as f64 - reference).abs();
+    assert!(f64_error < f32_error);
+    assert_eq!(stat.value(), reference as f32);
+    assert_eq!(stat.recompute_from_scratch(), stat.value());
+
+    let stat = Stat::<2>::builder()
+        .base(10f32)
+        .f64_accumulation(true)
+        .build();
+    assert!(stat.f64_accumulation());
+}
+
+#[test]
+fn clone_has_its_own_value_cell() {
+    let stat: Stat<2> = Stat::new(10f32);
+    let value_cell = stat.value_cell();
+    let mut clone = stat.clone();
+    let _modifier = clone.add_modifier(StatModifier::Flat(100f32));
+    assert_eq!(clone.value(), 110f32);
+    assert_eq!(stat.value(), 10f32);
+    assert_eq!(value_cell.get(), 10f32);
+    assert_eq!(clone.value_cell().get(), 110f32);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn debug_validate() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    stat.debug_validate();
+    stat.set_bounds(Some(0f32), Some(50f32));
+    let _modifier_multiply = stat.add_modifier(StatModifier::PercentMultiply(2f32));
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(5f32));
+    let dropped = stat.add_modifier_with_order(StatModifier::Flat(100f32), -1);
+    remove_modifier(&mut stat, dropped);
+    let _modifier_ceil = stat.add_modifier_with_order(StatModifier::Ceil(20f32), 1);
+    for mut modifier in stat.modifiers_mut().iter_mut() {
+        modifier.set_order(-modifier.order());
+    }
+    stat.debug_validate();
+    // multiply, ceil, flat
+    assert_eq!(stat.value(), 25f32);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn ron_modifier_defs() {
+    let defs: Vec<ModifierDef> = ron::from_str(
+        "[
+            (modifier: Flat(5.0), order: 0),
+            (modifier: PercentMultiply(2.0), order: 2, tag: Some(3), source: Skill),
+        ]",
+    )
+    .unwrap();
+    let (stat, _handles) = Stat::<2>::from_base_and_defs(10f32, defs.iter().copied());
+    assert_eq!(stat.value(), 30f32);
+    assert_eq!(stat.count_by_tag(3), 1);
+
+    let text = ron::to_string(&defs).unwrap();
+    assert_eq!(
+        text,
+        "[(modifier:Flat(5.0),order:0),(modifier:PercentMultiply(2.0),order:2,tag:Some(3),source:Skill)]"
+    );
+
+    let kinds: Vec<ModifierKind> = ron::from_str("[Flat, Diminishing]").unwrap();
+    assert_eq!(kinds, vec![ModifierKind::Flat, ModifierKind::Diminishing]);
+    assert_eq!(ron::to_string(&ModifierKind::Ceil).unwrap(), "Ceil");
+}
+
+#[cfg(feature = "history")]
+#[test]
+fn history() {
+    let mut stat: Stat<4> = Stat::new(10f32);
+    assert!(stat.history().is_empty());
+
+    let modifier_1 = stat.add_modifier(StatModifier::Flat(1f32));
+    assert_eq!(stat.value(), 11f32);
+    let _modifier_2 = stat.add_modifier(StatModifier::Flat(2f32));
+    assert_eq!(*stat.history(), [11f32, 13f32]);
+
+    // only the value the stat settled on is recorded, once
+    stat.set_bounds(Some(0f32), Some(12f32));
+    stat.set_bounds(None, None);
+    assert_eq!(stat.value(), 13f32);
+    let mut other: Stat<4> = Stat::new(0f32);
+    let _other_modifier = other.add_modifier(StatModifier::Flat(4f32));
+    assert_eq!(stat.value_with_integrated_modifiers(&other), 17f32);
+    assert_eq!(*stat.history(), [11f32, 13f32]);
+
+    remove_modifier(&mut stat, modifier_1);
+    let _modifier_3 = stat.add_modifier(StatModifier::Flat(3f32));
+    assert_eq!(*stat.history(), [11f32, 13f32, 15f32]);
+
+    let stat: Stat<8> = stat.into_capacity().unwrap();
+    assert_eq!(*stat.history(), [11f32, 13f32, 15f32]);
+}
+
+#[cfg(feature = "history")]
+#[test]
+fn history_keeps_the_latest_values() {
+    let mut stat: Stat<2> = Stat::new(0f32);
+    for base_value in 1..=game_stat::HISTORY_CAPACITY + 2 {
+        *stat.base_value_mut() = base_value as f32;
+        stat.value();
+    }
+    let history = stat.history();
+    assert_eq!(history.len(), game_stat::HISTORY_CAPACITY);
+    // the oldest values were dropped
+    assert_eq!(history.first(), Some(&3f32));
+    assert_eq!(
+        history.last(),
+        Some(&(game_stat::HISTORY_CAPACITY as f32 + 2f32))
+    );
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn rolled_modifiers() {
+    use rand::SeedableRng;
+
+    let roll = |seed| {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        (
+            StatModifier::roll_flat(5f32..10f32, &mut rng),
+            StatModifier::roll_percent(0.1f32..=0.2f32, &mut rng),
+        )
+    };
+    let (flat, percent) = roll(42);
+    let StatModifier::Flat(flat_value) = flat else {
+        panic!("expected a flat modifier");
+    };
+    let StatModifier::PercentAdd(percent_value) = percent else {
+        panic!("expected a percent modifier");
+    };
+    assert!((5f32..10f32).contains(&flat_value));
+    assert!((0.1f32..=0.2f32).contains(&percent_value));
+
+    // same seed, same rolls
+    let (flat_again, percent_again) = roll(42);
+    assert!(matches!(flat_again, StatModifier::Flat(v) if v == flat_value));
+    assert!(matches!(percent_again, StatModifier::PercentAdd(v) if v == percent_value));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn jitter() {
+    use rand::SeedableRng;
+
+    let spawn = |seed| {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let mut stat: Stat<2> = Stat::new(100f32);
+        let modifier = stat.with_jitter(-0.1f32..=0.1f32, &mut rng);
+        (stat, modifier)
+    };
+    let (stat, modifier) = spawn(7);
+    let StatModifier::PercentAdd(jitter) = modifier else {
+        panic!("expected a percent modifier");
+    };
+    assert!((-0.1f32..=0.1f32).contains(&jitter));
+    assert!((90f32..=110f32).contains(&stat.value()));
+    assert_eq!(stat.len(), 1);
+
+    // same seed, same spawn
+    let (stat_again, _) = spawn(7);
+    assert_eq!(stat_again.value(), stat.value());
+}
+
+#[cfg(feature = "manual-pruning")]
+#[test]
+fn manual_pruning() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let modifier_1 = stat.add_modifier(StatModifier::Flat(5f32));
+    let modifier_2 = stat.add_modifier(StatModifier::Flat(5f32));
+    // dropped handles don't remove anything
+    drop(modifier_2);
+    assert_eq!(stat.value(), 20f32);
+    assert_eq!(stat.len(), 2);
+
+    assert!(stat.remove_modifier(&modifier_1));
+    assert!(!stat.remove_modifier(&modifier_1));
+    assert_eq!(stat.value(), 15f32);
+}
+
+#[cfg(feature = "manual-pruning")]
+#[test]
+fn manual_pruning_temporary_modifiers() {
+    let mut stat: Stat<2> = Stat::new(10f32);
+    let _modifier_flat = stat.add_modifier(StatModifier::Flat(5f32));
+    let mut other_stat: Stat<2> = Stat::new(0f32);
+    let _modifier_other = other_stat.add_modifier(StatModifier::Flat(5f32));
+    // the integrated modifiers don't stay behind and compound
+    assert_eq!(stat.value_with_integrated_modifiers(&other_stat), 20f32);
+    assert_eq!(stat.value_with_integrated_modifiers(&other_stat), 20f32);
+    assert_eq!(stat.len(), 1);
+    assert_eq!(stat.value(), 15f32);
+
+    // an uncommitted staged modifier is discarded when dropped
+    let staged = stat.stage_modifier(StatModifier::Flat(1f32));
+    assert_eq!(stat.len(), 2);
+    drop(staged);
+    assert_eq!(stat.len(), 1);
+    // once committed it stays until removed
+    let staged = stat.stage_modifier(StatModifier::Flat(1f32));
+    let modifier_committed = stat.commit_staged(staged).unwrap();
+    drop(modifier_committed.clone());
+    assert_eq!(stat.value(), 16f32);
+    assert!(stat.remove_modifier(&modifier_committed));
+    assert_eq!(stat.value(), 15f32);
+}
+
+#[cfg(feature = "manual-pruning")]
+#[test]
+fn manual_pruning_scheduler() {
+    let mut scheduler = game_stat::ModifierScheduler::new();
+    let mut stat: Stat<2> = Stat::new(10f32);
+    scheduler.schedule(stat.add_modifier(StatModifier::Flat(5f32)), 1f32);
+    scheduler.schedule(stat.add_modifier(StatModifier::Flat(1f32)), 2f32);
+    // advance() only frees the handles
+    assert_eq!(scheduler.advance(1f32), 1);
+    assert_eq!(stat.value(), 16f32);
+
+    let expired = scheduler.advance_expired(1f32);
+    assert_eq!(expired.len(), 1);
+    for handle in expired {
+        assert!(stat.remove_modifier(&handle));
+    }
+    assert_eq!(stat.value(), 15f32);
+    assert!(scheduler.is_empty());
+}
+
+#[cfg(all(feature = "sync", not(feature = "manual-pruning")))]
+#[test]
+pub fn multithreaded_environment() {
+    use std::sync::{Arc, Mutex};
+    use std::thread::*;
+
+    let stat = Arc::new(Mutex::new(Stat::<2>::new(0.0f32)));
+    let stat_thread_1 = stat.clone();
+    let stat_thread_2 = stat.clone();
+    let handle_1 = spawn(move || {
+        let _modifier_handle = stat_thread_1
+            .lock()
+            .unwrap()
+            .add_modifier(StatModifier::Flat(1.0));
+    });
+    let handle_2 = spawn(move || {
+        let _modifier_handle = stat_thread_2
+            .lock()
+            .unwrap()
+            .add_modifier(StatModifier::Flat(1.0));
+    });
+    handle_1.join().unwrap();
+    handle_2.join().unwrap();
+    assert!(stat.lock().unwrap().value() == 0.0f32);
+}
+
+#[test]
+fn inline_slot_size() {
+    // rarely used metadata lives out of line, a slot shouldn't grow with every modifier option
+    let per_slot = (std::mem::size_of::<Stat<16>>() - std::mem::size_of::<Stat<8>>()) / 8;
+    assert!(per_slot <= 64, "{per_slot} bytes per modifier slot");
+}
+
+#[test]
+fn local_and_sync_stats() {
+    use game_stat::{LocalStat, SyncStat};
+
+    let mut local: LocalStat<2> = LocalStat::new(10f32);
+    let local_handle: std::rc::Rc<_> = local.add_modifier(StatModifier::Flat(5f32));
+    let mut shared: SyncStat<2> = SyncStat::new(10f32);
+    let shared_handle: std::sync::Arc<_> = shared.add_modifier(StatModifier::Flat(2f32));
+    assert_eq!(local.value(), 15f32);
+
+    // the sync stat moves to another thread along with its handle, whatever the sync feature says
+    let mut shared = std::thread::spawn(move || {
+        assert_eq!(shared.value(), 12f32);
+        let handle = shared.add_modifier(StatModifier::PercentMultiply(2f32));
+        remove_modifier(&mut shared, shared_handle);
+        (shared, handle)
+    })
+    .join()
+    .unwrap();
+    assert_eq!(shared.0.value(), 20f32);
+    remove_modifier(&mut shared.0, shared.1);
+    assert_eq!(shared.0.value(), 10f32);
+
+    remove_modifier(&mut local, local_handle);
+    assert_eq!(local.value(), 10f32);
+}
+
+#[test]
+fn strategy_of_sheets_and_linked_stats() {
+    use game_stat::{ArcStrategy, LinkedStat, RcStrategy, SharedBase, SyncStat};
+
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SyncStat<2>>();
+    assert_send_sync::<StatModifierHandle<ArcStrategy>>();
+    assert_send_sync::<StatSheet<&str, 2, ArcStrategy>>();
+
+    let mut sheet: StatSheet<&str, 2, ArcStrategy> = StatSheet::new();
+    sheet.insert("armor", SyncStat::new(10f32));
+    let handle = sheet
+        .add_modifier("armor", StatModifier::Flat(5f32))
+        .unwrap();
+    let sheet = std::thread::spawn(move || {
+        assert_eq!(sheet.get("armor").unwrap().value(), 15f32);
+        sheet
+    })
+    .join()
+    .unwrap();
+    assert_eq!(sheet.get("armor").unwrap().value(), 15f32);
+    drop(handle);
+
+    let shield: SharedBase<RcStrategy> = SharedBase::with_value(10f32);
+    let mut hud_shield: LinkedStat<2, RcStrategy> = LinkedStat::new(&shield);
+    let _modifier = hud_shield.add_modifier(StatModifier::Flat(1f32));
+    shield.set(20f32);
+    assert_eq!(hud_shield.value(), 21f32);
+
+    let stats: Vec<Box<dyn game_stat::StatLike<RcStrategy>>> = vec![
+        Box::new(Stat::<2, RcStrategy>::new(1f32)),
+        Box::new(Stat::<4, RcStrategy>::new(2f32)),
+    ];
+    assert_eq!(stats.iter().map(|stat| stat.value()).sum::<f32>(), 3f32);
 }
@@ -0,0 +1,69 @@
+// run with: cargo test --features tracing --test tracing
+// the recorded events include pruning a dropped modifier, which doesn't happen with manual-pruning
+#![cfg(all(feature = "tracing", not(feature = "manual-pruning")))]
+use game_stat::prelude::*;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+// records the message of every event
+#[derive(Clone, Default)]
+struct MessageRecorder(Arc<Mutex<Vec<String>>>);
+
+struct MessageVisitor<'a>(&'a mut String);
+
+impl Visit for MessageVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            *self.0 = format!("{:?}", value);
+        }
+    }
+}
+
+impl Subscriber for MessageRecorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut message = String::new();
+        event.record(&mut MessageVisitor(&mut message));
+        self.0.lock().unwrap().push(message);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn events_on_add() {
+    let recorder = MessageRecorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let mut stat: Stat<2> = Stat::new(10f32);
+        {
+            let _handle = stat.add_modifier(StatModifier::Flat(1.0));
+        }
+        assert_eq!(stat.value(), 10f32);
+    });
+
+    let messages = recorder.0.lock().unwrap();
+    assert_eq!(
+        *messages,
+        vec![
+            "added modifier",
+            "calculated value",
+            "pruned modifiers",
+            "calculated value"
+        ]
+    );
+}